@@ -0,0 +1,410 @@
+// NOTE: #[derive(Alphabet)] writes the Mono, IMono and NucleicAcid impls
+// for a fieldless enum, plus the cell type and Cat to go with it.
+// The generated code names the monomer, once_mono, polymer and category
+// crates directly, so the deriving crate must depend on all four.
+//
+// #[derive(Debug, PartialEq, Clone, Alphabet)]
+// #[alphabet(cat = "DNACat", cell = "DNACell", polymer = "Helix", nucleic)]
+// pub enum DNA {
+//     #[base(letter = 'A', complement = T)]
+//     A,
+//     #[base(letter = 'T', complement = A)]
+//     T,
+//     #[base(letter = 'G', complement = C, gc)]
+//     G,
+//     #[base(letter = 'C', complement = G, gc)]
+//     C,
+// }
+//
+// complement makes the alphabet an IMono, and must then be on every
+// variant. nucleic adds NucleicAcid and NCat, counting the gc variants,
+// and reading the single bases off the letters A, C, G and T or U.
+// name overrides the alphabet reported in parse errors. The Cat also gets
+// category::Intern, so serde users can read cells back through it.
+extern crate proc_macro;
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{format_ident, quote};
+use syn::{parse_macro_input, Data, DeriveInput, Error, Fields, Ident, LitChar, LitStr};
+
+struct Options {
+    name: Option<String>,
+    cat: Ident,
+    cell: Ident,
+    polymer: Ident,
+    nucleic: bool,
+}
+
+struct Base {
+    variant: Ident,
+    field: Ident,
+    letter: u8,
+    complement: Option<Ident>,
+    gc: bool,
+}
+
+#[proc_macro_derive(Alphabet, attributes(alphabet, base))]
+pub fn derive_alphabet(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match expand(&input) {
+        Ok(x) => x.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
+fn options(input: &DeriveInput) -> Result<Options, Error> {
+    let mut name = None;
+    let mut cat = None;
+    let mut cell = None;
+    let mut polymer = None;
+    let mut nucleic = false;
+
+    for attr in input.attrs.iter().filter(|a| a.path().is_ident("alphabet")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("nucleic") {
+                nucleic = true;
+                return Ok(());
+            }
+
+            let value: LitStr = meta.value()?.parse()?;
+            if meta.path.is_ident("name") {
+                name = Some(value.value());
+            } else if meta.path.is_ident("cat") {
+                cat = Some(value.parse()?);
+            } else if meta.path.is_ident("cell") {
+                cell = Some(value.parse()?);
+            } else if meta.path.is_ident("polymer") {
+                polymer = Some(value.parse()?);
+            } else {
+                return Err(meta.error("expected name, cat, cell, polymer or nucleic"));
+            }
+            Ok(())
+        })?;
+    }
+
+    let missing = |key: &str| Error::new(Span::call_site(), format!("#[alphabet] needs {}", key));
+
+    Ok(Options {
+        name,
+        cat: cat.ok_or_else(|| missing("cat"))?,
+        cell: cell.ok_or_else(|| missing("cell"))?,
+        polymer: polymer.unwrap_or_else(|| Ident::new("Strand", Span::call_site())),
+        nucleic,
+    })
+}
+
+fn bases(input: &DeriveInput) -> Result<Vec<Base>, Error> {
+    let data = match &input.data {
+        Data::Enum(x) => x,
+        _ => {
+            return Err(Error::new_spanned(
+                input,
+                "Alphabet can only be derived for enums",
+            ))
+        }
+    };
+
+    let mut next = Vec::<Base>::new();
+
+    for v in data.variants.iter() {
+        if !matches!(v.fields, Fields::Unit) {
+            return Err(Error::new_spanned(
+                v,
+                "Alphabet variants cannot hold fields",
+            ));
+        }
+
+        let mut letter = None;
+        let mut complement = None;
+        let mut gc = false;
+
+        for attr in v.attrs.iter().filter(|a| a.path().is_ident("base")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("gc") {
+                    gc = true;
+                } else if meta.path.is_ident("letter") {
+                    let c: LitChar = meta.value()?.parse()?;
+                    match c.value().is_ascii_alphabetic() {
+                        true => letter = Some(c.value().to_ascii_uppercase() as u8),
+                        _ => return Err(Error::new_spanned(c, "letters must be ASCII alphabetic")),
+                    }
+                } else if meta.path.is_ident("complement") {
+                    complement = Some(meta.value()?.parse()?);
+                } else {
+                    return Err(meta.error("expected letter, complement or gc"));
+                }
+                Ok(())
+            })?;
+        }
+
+        let letter = letter.ok_or_else(|| Error::new_spanned(v, "missing #[base(letter = ..)]"))?;
+        if next.iter().any(|b| b.letter == letter) {
+            return Err(Error::new_spanned(v, "letter is used by another variant"));
+        }
+
+        next.push(Base {
+            variant: v.ident.clone(),
+            field: format_ident!("{}", v.ident.to_string().to_lowercase()),
+            letter,
+            complement,
+            gc,
+        });
+    }
+
+    let with = next.iter().filter(|b| b.complement.is_some()).count();
+    if with != 0 && with != next.len() {
+        return Err(Error::new_spanned(
+            input,
+            "complement must be given for every variant or none",
+        ));
+    }
+
+    for b in next.iter() {
+        if let Some(c) = &b.complement {
+            if !next.iter().any(|x| &x.variant == c) {
+                return Err(Error::new_spanned(
+                    c,
+                    "complement is not a variant of this enum",
+                ));
+            }
+        }
+    }
+
+    Ok(next)
+}
+
+fn expand(input: &DeriveInput) -> Result<TokenStream2, Error> {
+    let o = options(input)?;
+    let bases = bases(input)?;
+
+    let imono = bases.iter().all(|b| b.complement.is_some()) && !bases.is_empty();
+    if o.nucleic && !imono {
+        return Err(Error::new_spanned(
+            input,
+            "nucleic alphabets need a complement for every variant",
+        ));
+    }
+
+    let vis = &input.vis;
+    let name = &input.ident;
+    let cat = &o.cat;
+    let cell = &o.cell;
+    let polymer = &o.polymer;
+
+    let letters: String = bases.iter().map(|b| b.letter as char).collect();
+    let alphabet = o
+        .name
+        .clone()
+        .unwrap_or_else(|| format!("{} ({})", name, letters));
+
+    let variants: Vec<&Ident> = bases.iter().map(|b| &b.variant).collect();
+    let fields: Vec<&Ident> = bases.iter().map(|b| &b.field).collect();
+    let uppers: Vec<u8> = bases.iter().map(|b| b.letter).collect();
+
+    let wrapper = match imono {
+        true => quote!(::once_mono::IMonomer),
+        _ => quote!(::once_mono::Monomer),
+    };
+
+    let mut out = quote! {
+        impl ::monomer::Mono for #name {
+            const ALPHABET: &'static str = #alphabet;
+
+            fn from_string(s: String) -> Result<#name, ::monomer::ParseError> {
+                match <#name as ::monomer::Mono>::from_bytes(s.as_bytes()) {
+                    Ok(x) => Ok(x),
+                    Err(_) => Err(::monomer::ParseError::monomer(s, #alphabet)),
+                }
+            }
+
+            fn from_bytes(b: &[u8]) -> Result<#name, ::monomer::ParseError> {
+                static TABLE: [Option<#name>; 256] = {
+                    const NONE: Option<#name> = None;
+                    let mut t = [NONE; 256];
+                    let mut i = 0;
+                    while i < 256 {
+                        t[i] = match (i as u8).to_ascii_uppercase() {
+                            #(#uppers => Some(#name::#variants),)*
+                            _ => None,
+                        };
+                        i += 1;
+                    }
+                    t
+                };
+
+                match b {
+                    [x] => TABLE[*x as usize]
+                        .clone()
+                        .ok_or_else(|| ::monomer::ParseError::monomer_bytes(b, #alphabet)),
+                    _ => Err(::monomer::ParseError::monomer_bytes(b, #alphabet)),
+                }
+            }
+        }
+
+        #vis type #cell = #wrapper<#name>;
+
+        #vis struct #cat {
+            #(pub #fields: #cell,)*
+        }
+
+        impl #cat {
+            pub fn cell(&self, x: &#name) -> #cell {
+                match x {
+                    #(#name::#variants => self.#fields.clone(),)*
+                }
+            }
+        }
+
+        impl ::category::Intern for #cat {
+            type Value = #name;
+            type Cell = #cell;
+
+            fn intern(&self, x: &#name) -> #cell {
+                self.cell(x)
+            }
+        }
+
+        impl ::category::Cat<#cell, ::polymer::#polymer<#cell>> for #cat {
+            fn new() -> Self {
+                #cat {
+                    #(#fields: <#cell as ::monomer::Mono>::from_byte(#uppers).unwrap(),)*
+                }
+            }
+
+            fn monomer_from_string(&self, s: String) -> Result<#cell, ::monomer::ParseError> {
+                <#name as ::monomer::Mono>::from_string(s).map(|x| self.cell(&x))
+            }
+
+            fn monomer_from_bytes(&self, b: &[u8]) -> Result<#cell, ::monomer::ParseError> {
+                <#name as ::monomer::Mono>::from_bytes(b).map(|x| self.cell(&x))
+            }
+
+            fn polymer_from_string(
+                &self,
+                s: String,
+            ) -> Result<::polymer::#polymer<#cell>, ::monomer::ParseError> {
+                let mut x = <::polymer::#polymer<#cell> as ::polymer::Polymer<#cell>>::new();
+
+                for (i, c) in s.chars().enumerate() {
+                    match self.monomer_from_string(c.to_string()) {
+                        Ok(y) => ::polymer::Polymer::push(&mut x, y),
+                        Err(e) => return Err(e.offset_by(i)),
+                    }
+                }
+
+                Ok(x)
+            }
+        }
+    };
+
+    if imono {
+        let complements: Vec<&Ident> = bases
+            .iter()
+            .map(|b| b.complement.as_ref().unwrap())
+            .collect();
+        let complement_fields: Vec<Ident> = complements
+            .iter()
+            .map(|c| format_ident!("{}", c.to_string().to_lowercase()))
+            .collect();
+
+        out.extend(quote! {
+            impl ::monomer::IMono for #name {
+                fn inverse(c: &Self) -> Self {
+                    match c {
+                        #(#name::#variants => #name::#complements,)*
+                    }
+                }
+            }
+
+            impl ::category::ICat<#cell, ::polymer::#polymer<#cell>> for #cat {
+                fn inverse_m(&self, t: &#cell) -> #cell {
+                    match t.read().as_ref().unwrap() {
+                        #(#name::#variants => self.#complement_fields.clone(),)*
+                    }
+                }
+
+                fn inverse_p(&self, u: &::polymer::#polymer<#cell>) -> ::polymer::#polymer<#cell> {
+                    ::polymer::Polymer::iter(u)
+                        .rev()
+                        .map(|z| self.inverse_m(&z))
+                        .collect()
+                }
+            }
+        });
+    }
+
+    if o.nucleic {
+        let gc: Vec<&Ident> = bases.iter().filter(|b| b.gc).map(|b| &b.variant).collect();
+        let gc_check = match gc.is_empty() {
+            true => quote!(false),
+            _ => quote!(matches!(self, #(#name::#gc)|*)),
+        };
+
+        let is = |letters: &[u8]| {
+            let x: Vec<&Ident> = bases
+                .iter()
+                .filter(|b| letters.contains(&b.letter))
+                .map(|b| &b.variant)
+                .collect();
+            match x.is_empty() {
+                true => quote!(false),
+                _ => quote!(matches!(self, #(#name::#x)|*)),
+            }
+        };
+        let (is_g, is_c, is_a, is_t) = (is(b"G"), is(b"C"), is(b"A"), is(b"TU"));
+
+        out.extend(quote! {
+            impl ::monomer::NucleicAcid for #name {
+                fn is_g_or_c(&self) -> bool {
+                    #gc_check
+                }
+
+                fn is_g(&self) -> bool {
+                    #is_g
+                }
+
+                fn is_c(&self) -> bool {
+                    #is_c
+                }
+
+                fn is_a(&self) -> bool {
+                    #is_a
+                }
+
+                fn is_t(&self) -> bool {
+                    #is_t
+                }
+            }
+
+            impl ::category::NCat<#cell, ::polymer::#polymer<#cell>> for #cat {
+                fn gc_content(u: ::polymer::#polymer<#cell>) -> (u64, u64) {
+                    let mut d = 0;
+                    let mut n = 0;
+                    for x in u {
+                        d += 1;
+                        if ::monomer::NucleicAcid::is_g_or_c(&x) {
+                            n += 1;
+                        }
+                    }
+
+                    (n, d)
+                }
+            }
+        });
+    }
+
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    // NOTE: The .stderr files are trybuild's, rewrite them with
+    // TRYBUILD=overwrite after changing an error.
+    #[test]
+    fn ui() {
+        let t = trybuild::TestCases::new();
+        t.pass("tests/ui/pass.rs");
+        t.compile_fail("tests/ui/fail/*.rs");
+    }
+}
@@ -0,0 +1,9 @@
+use alphabet::Alphabet;
+
+#[derive(Debug, PartialEq, Clone, Alphabet)]
+#[alphabet(cat = "PairCat", cell = "PairCell")]
+pub struct Pair {
+    a: u8,
+}
+
+fn main() {}
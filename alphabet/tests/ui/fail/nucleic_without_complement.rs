@@ -0,0 +1,12 @@
+use alphabet::Alphabet;
+
+#[derive(Debug, PartialEq, Clone, Alphabet)]
+#[alphabet(cat = "PairCat", cell = "PairCell", nucleic)]
+pub enum Pair {
+    #[base(letter = 'A')]
+    A,
+    #[base(letter = 'T')]
+    T,
+}
+
+fn main() {}
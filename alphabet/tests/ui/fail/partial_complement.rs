@@ -0,0 +1,12 @@
+use alphabet::Alphabet;
+
+#[derive(Debug, PartialEq, Clone, Alphabet)]
+#[alphabet(cat = "PairCat", cell = "PairCell")]
+pub enum Pair {
+    #[base(letter = 'A', complement = T)]
+    A,
+    #[base(letter = 'T')]
+    T,
+}
+
+fn main() {}
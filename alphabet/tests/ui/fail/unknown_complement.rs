@@ -0,0 +1,12 @@
+use alphabet::Alphabet;
+
+#[derive(Debug, PartialEq, Clone, Alphabet)]
+#[alphabet(cat = "PairCat", cell = "PairCell")]
+pub enum Pair {
+    #[base(letter = 'A', complement = U)]
+    A,
+    #[base(letter = 'T', complement = A)]
+    T,
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+use alphabet::Alphabet;
+
+#[derive(Debug, PartialEq, Clone, Alphabet)]
+#[alphabet(cat = "PairCat", cell = "PairCell")]
+pub enum Pair {
+    #[base(letter = 'A')]
+    A,
+    #[base(letter = 'T')]
+    T(u8),
+}
+
+fn main() {}
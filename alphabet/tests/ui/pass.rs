@@ -0,0 +1,33 @@
+use alphabet::Alphabet;
+use category::Cat;
+use monomer::IMono;
+use polymer::Polymer;
+
+#[derive(Debug, PartialEq, Clone, Alphabet)]
+#[alphabet(cat = "PairCat", cell = "PairCell", nucleic)]
+pub enum Pair {
+    #[base(letter = 'A', complement = T)]
+    A,
+    #[base(letter = 'T', complement = A)]
+    T,
+    #[base(letter = 'G', complement = C, gc)]
+    G,
+    #[base(letter = 'C', complement = G, gc)]
+    C,
+}
+
+#[derive(Debug, PartialEq, Clone, Alphabet)]
+#[alphabet(name = "coin", cat = "CoinCat", cell = "CoinCell")]
+pub enum Coin {
+    #[base(letter = 'H')]
+    Heads,
+    #[base(letter = 't')]
+    Tails,
+}
+
+fn main() {
+    assert_eq!(Pair::inverse(&Pair::G), Pair::C);
+
+    let c = CoinCat::new();
+    assert_eq!(c.polymer_from_string(String::from("hT")).unwrap().len(), 2);
+}
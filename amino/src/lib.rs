@@ -1,12 +1,14 @@
-use category::Cat;
-use monomer::Mono;
+use category::{Cat, Intern};
+use monomer::{Mono, ParseError};
 use once_mono::Monomer;
 use polymer::{Polymer, Strand};
 use rna::{RNACat, RNACell};
+use std::fmt;
 
 type RNACodon = (RNACell, RNACell, RNACell);
 
 #[derive(PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Amino {
     START,
     STOP,
@@ -30,42 +32,392 @@ pub enum Amino {
     Trp,
     Tyr,
     Val,
+    // Selenocysteine, U, read through UGA.
+    Sec,
+    // Pyrrolysine, O, read through UAG.
+    Pyl,
+    // B, Asn or Asp.
+    Asx,
+    // Z, Gln or Glu.
+    Glx,
+    // J, Leu or Ile.
+    Xle,
+    // X, any residue.
+    Xaa,
 }
 
 // TODO: MAKE COMPLIANT WITH GAF -> Gene Association Files.
 // For now, a poor folk's implementation imagining Protiens as CSV.
 impl Mono for Amino {
-    fn from_string(s: String) -> Option<Amino> {
+    const ALPHABET: &'static str = "amino acid (three-letter codes)";
+    const WIDTH: usize = 3;
+
+    fn from_string(s: String) -> Result<Amino, ParseError> {
         match s.to_uppercase().as_ref() {
-            "START" => Some(Amino::START),
-            "STOP" => Some(Amino::STOP),
-            "ALA" => Some(Amino::Ala),
-            "ARG" => Some(Amino::Arg),
-            "ASN" => Some(Amino::Asn),
-            "ASP" => Some(Amino::Asp),
-            "CYS" => Some(Amino::Cys),
-            "GLN" => Some(Amino::Gln),
-            "GLU" => Some(Amino::Glu),
-            "GLY" => Some(Amino::Gly),
-            "HIS" => Some(Amino::His),
-            "ILE" => Some(Amino::Ile),
-            "LEU" => Some(Amino::Leu),
-            "LYS" => Some(Amino::Lys),
-            "MET" => Some(Amino::Met),
-            "PHE" => Some(Amino::Phe),
-            "PRO" => Some(Amino::Pro),
-            "SER" => Some(Amino::Ser),
-            "THR" => Some(Amino::Thr),
-            "TRP" => Some(Amino::Trp),
-            "TYR" => Some(Amino::Tyr),
-            "VAL" => Some(Amino::Val),
+            "START" => Ok(Amino::START),
+            "STOP" => Ok(Amino::STOP),
+            "ALA" => Ok(Amino::Ala),
+            "ARG" => Ok(Amino::Arg),
+            "ASN" => Ok(Amino::Asn),
+            "ASP" => Ok(Amino::Asp),
+            "CYS" => Ok(Amino::Cys),
+            "GLN" => Ok(Amino::Gln),
+            "GLU" => Ok(Amino::Glu),
+            "GLY" => Ok(Amino::Gly),
+            "HIS" => Ok(Amino::His),
+            "ILE" => Ok(Amino::Ile),
+            "LEU" => Ok(Amino::Leu),
+            "LYS" => Ok(Amino::Lys),
+            "MET" => Ok(Amino::Met),
+            "PHE" => Ok(Amino::Phe),
+            "PRO" => Ok(Amino::Pro),
+            "SER" => Ok(Amino::Ser),
+            "THR" => Ok(Amino::Thr),
+            "TRP" => Ok(Amino::Trp),
+            "TYR" => Ok(Amino::Tyr),
+            "VAL" => Ok(Amino::Val),
+            "SEC" => Ok(Amino::Sec),
+            "PYL" => Ok(Amino::Pyl),
+            "ASX" => Ok(Amino::Asx),
+            "GLX" => Ok(Amino::Glx),
+            "XLE" => Ok(Amino::Xle),
+            "XAA" => Ok(Amino::Xaa),
+            _ => Err(ParseError::monomer(s, Self::ALPHABET)),
+        }
+    }
+
+    // NOTE: Upper-cases into a stack buffer, long enough for START.
+    fn from_bytes(b: &[u8]) -> Result<Amino, ParseError> {
+        let mut x = [0u8; 5];
+        if b.len() > x.len() {
+            return Err(ParseError::monomer_bytes(b, Self::ALPHABET));
+        }
+
+        for (i, c) in b.iter().enumerate() {
+            x[i] = c.to_ascii_uppercase();
+        }
+
+        match &x[..b.len()] {
+            b"START" => Ok(Amino::START),
+            b"STOP" => Ok(Amino::STOP),
+            b"ALA" => Ok(Amino::Ala),
+            b"ARG" => Ok(Amino::Arg),
+            b"ASN" => Ok(Amino::Asn),
+            b"ASP" => Ok(Amino::Asp),
+            b"CYS" => Ok(Amino::Cys),
+            b"GLN" => Ok(Amino::Gln),
+            b"GLU" => Ok(Amino::Glu),
+            b"GLY" => Ok(Amino::Gly),
+            b"HIS" => Ok(Amino::His),
+            b"ILE" => Ok(Amino::Ile),
+            b"LEU" => Ok(Amino::Leu),
+            b"LYS" => Ok(Amino::Lys),
+            b"MET" => Ok(Amino::Met),
+            b"PHE" => Ok(Amino::Phe),
+            b"PRO" => Ok(Amino::Pro),
+            b"SER" => Ok(Amino::Ser),
+            b"THR" => Ok(Amino::Thr),
+            b"TRP" => Ok(Amino::Trp),
+            b"TYR" => Ok(Amino::Tyr),
+            b"VAL" => Ok(Amino::Val),
+            b"SEC" => Ok(Amino::Sec),
+            b"PYL" => Ok(Amino::Pyl),
+            b"ASX" => Ok(Amino::Asx),
+            b"GLX" => Ok(Amino::Glx),
+            b"XLE" => Ok(Amino::Xle),
+            b"XAA" => Ok(Amino::Xaa),
+            _ => Err(ParseError::monomer_bytes(b, Self::ALPHABET)),
+        }
+    }
+}
+
+impl Amino {
+    pub const ONE_LETTER: &'static str = "amino acid (one-letter codes)";
+
+    // The twenty residues of the standard genetic code.
+    pub const STANDARD: [Amino; 20] = [
+        Amino::Ala,
+        Amino::Arg,
+        Amino::Asn,
+        Amino::Asp,
+        Amino::Cys,
+        Amino::Gln,
+        Amino::Glu,
+        Amino::Gly,
+        Amino::His,
+        Amino::Ile,
+        Amino::Leu,
+        Amino::Lys,
+        Amino::Met,
+        Amino::Phe,
+        Amino::Pro,
+        Amino::Ser,
+        Amino::Thr,
+        Amino::Trp,
+        Amino::Tyr,
+        Amino::Val,
+    ];
+
+    pub fn is_ambiguous(&self) -> bool {
+        matches!(self, Amino::Asx | Amino::Glx | Amino::Xle | Amino::Xaa)
+    }
+
+    // NOTE: The residues an ambiguity code stands for, any other residue
+    // stands for just itself.
+    pub fn members(&self) -> Vec<Amino> {
+        match self {
+            Amino::Asx => vec![Amino::Asn, Amino::Asp],
+            Amino::Glx => vec![Amino::Gln, Amino::Glu],
+            Amino::Xle => vec![Amino::Leu, Amino::Ile],
+            Amino::Xaa => Self::STANDARD.to_vec(),
+            x => vec![x.clone()],
+        }
+    }
+
+    pub fn three_letter(&self) -> &'static str {
+        match self {
+            Amino::START => "START",
+            Amino::STOP => "STOP",
+            Amino::Ala => "Ala",
+            Amino::Arg => "Arg",
+            Amino::Asn => "Asn",
+            Amino::Asp => "Asp",
+            Amino::Cys => "Cys",
+            Amino::Gln => "Gln",
+            Amino::Glu => "Glu",
+            Amino::Gly => "Gly",
+            Amino::His => "His",
+            Amino::Ile => "Ile",
+            Amino::Leu => "Leu",
+            Amino::Lys => "Lys",
+            Amino::Met => "Met",
+            Amino::Phe => "Phe",
+            Amino::Pro => "Pro",
+            Amino::Ser => "Ser",
+            Amino::Thr => "Thr",
+            Amino::Trp => "Trp",
+            Amino::Tyr => "Tyr",
+            Amino::Val => "Val",
+            Amino::Sec => "Sec",
+            Amino::Pyl => "Pyl",
+            Amino::Asx => "Asx",
+            Amino::Glx => "Glx",
+            Amino::Xle => "Xle",
+            Amino::Xaa => "Xaa",
+        }
+    }
+
+    // NOTE: START has no one-letter code, translation reads it as Met.
+    pub fn one_letter(&self) -> Option<char> {
+        match self {
+            Amino::START => None,
+            Amino::STOP => Some('*'),
+            Amino::Ala => Some('A'),
+            Amino::Arg => Some('R'),
+            Amino::Asn => Some('N'),
+            Amino::Asp => Some('D'),
+            Amino::Cys => Some('C'),
+            Amino::Gln => Some('Q'),
+            Amino::Glu => Some('E'),
+            Amino::Gly => Some('G'),
+            Amino::His => Some('H'),
+            Amino::Ile => Some('I'),
+            Amino::Leu => Some('L'),
+            Amino::Lys => Some('K'),
+            Amino::Met => Some('M'),
+            Amino::Phe => Some('F'),
+            Amino::Pro => Some('P'),
+            Amino::Ser => Some('S'),
+            Amino::Thr => Some('T'),
+            Amino::Trp => Some('W'),
+            Amino::Tyr => Some('Y'),
+            Amino::Val => Some('V'),
+            Amino::Sec => Some('U'),
+            Amino::Pyl => Some('O'),
+            Amino::Asx => Some('B'),
+            Amino::Glx => Some('Z'),
+            Amino::Xle => Some('J'),
+            Amino::Xaa => Some('X'),
+        }
+    }
+
+    pub fn from_one_letter(b: u8) -> Result<Amino, ParseError> {
+        match b.to_ascii_uppercase() {
+            b'*' => Ok(Amino::STOP),
+            b'A' => Ok(Amino::Ala),
+            b'R' => Ok(Amino::Arg),
+            b'N' => Ok(Amino::Asn),
+            b'D' => Ok(Amino::Asp),
+            b'C' => Ok(Amino::Cys),
+            b'Q' => Ok(Amino::Gln),
+            b'E' => Ok(Amino::Glu),
+            b'G' => Ok(Amino::Gly),
+            b'H' => Ok(Amino::His),
+            b'I' => Ok(Amino::Ile),
+            b'L' => Ok(Amino::Leu),
+            b'K' => Ok(Amino::Lys),
+            b'M' => Ok(Amino::Met),
+            b'F' => Ok(Amino::Phe),
+            b'P' => Ok(Amino::Pro),
+            b'S' => Ok(Amino::Ser),
+            b'T' => Ok(Amino::Thr),
+            b'W' => Ok(Amino::Trp),
+            b'Y' => Ok(Amino::Tyr),
+            b'V' => Ok(Amino::Val),
+            b'U' => Ok(Amino::Sec),
+            b'O' => Ok(Amino::Pyl),
+            b'B' => Ok(Amino::Asx),
+            b'Z' => Ok(Amino::Glx),
+            b'J' => Ok(Amino::Xle),
+            b'X' => Ok(Amino::Xaa),
+            _ => Err(ParseError::monomer_bytes(&[b], Self::ONE_LETTER)),
+        }
+    }
+}
+
+// NOTE: Residue masses are of the residue in a chain, a free amino acid
+// less one water, so a protein weighs the sum of its residues plus
+// WATER. None where a code has no one value: START, STOP, and the
+// ambiguity codes other than Xle, whose members weigh the same.
+impl Amino {
+    pub const WATER_AVERAGE: f64 = 18.01524;
+    pub const WATER_MONOISOTOPIC: f64 = 18.01056;
+
+    // Pace et al. (1995), per residue and per cystine, at 280nm.
+    pub const CYSTINE_EXTINCTION: u32 = 125;
+
+    pub fn average_mass(&self) -> Option<f64> {
+        match self {
+            Amino::Ala => Some(71.0788),
+            Amino::Arg => Some(156.1875),
+            Amino::Asn => Some(114.1038),
+            Amino::Asp => Some(115.0886),
+            Amino::Cys => Some(103.1388),
+            Amino::Gln => Some(128.1307),
+            Amino::Glu => Some(129.1155),
+            Amino::Gly => Some(57.0519),
+            Amino::His => Some(137.1411),
+            Amino::Ile => Some(113.1594),
+            Amino::Leu => Some(113.1594),
+            Amino::Lys => Some(128.1741),
+            Amino::Met => Some(131.1926),
+            Amino::Phe => Some(147.1766),
+            Amino::Pro => Some(97.1167),
+            Amino::Ser => Some(87.0782),
+            Amino::Thr => Some(101.1051),
+            Amino::Trp => Some(186.2132),
+            Amino::Tyr => Some(163.176),
+            Amino::Val => Some(99.1326),
+            Amino::Sec => Some(150.0388),
+            Amino::Pyl => Some(237.2982),
+            Amino::Xle => Some(113.1594),
+            _ => None,
+        }
+    }
+
+    pub fn monoisotopic_mass(&self) -> Option<f64> {
+        match self {
+            Amino::Ala => Some(71.03711),
+            Amino::Arg => Some(156.10111),
+            Amino::Asn => Some(114.04293),
+            Amino::Asp => Some(115.02694),
+            Amino::Cys => Some(103.00919),
+            Amino::Gln => Some(128.05858),
+            Amino::Glu => Some(129.04259),
+            Amino::Gly => Some(57.02146),
+            Amino::His => Some(137.05891),
+            Amino::Ile => Some(113.08406),
+            Amino::Leu => Some(113.08406),
+            Amino::Lys => Some(128.09496),
+            Amino::Met => Some(131.04049),
+            Amino::Phe => Some(147.06841),
+            Amino::Pro => Some(97.05276),
+            Amino::Ser => Some(87.03203),
+            Amino::Thr => Some(101.04768),
+            Amino::Trp => Some(186.07931),
+            Amino::Tyr => Some(163.06333),
+            Amino::Val => Some(99.06841),
+            Amino::Sec => Some(150.95364),
+            Amino::Pyl => Some(237.14773),
+            Amino::Xle => Some(113.08406),
             _ => None,
         }
     }
+
+    // Kyte and Doolittle (1982).
+    pub fn hydropathy(&self) -> Option<f64> {
+        match self {
+            Amino::Ala => Some(1.8),
+            Amino::Arg => Some(-4.5),
+            Amino::Asn => Some(-3.5),
+            Amino::Asp => Some(-3.5),
+            Amino::Cys => Some(2.5),
+            Amino::Gln => Some(-3.5),
+            Amino::Glu => Some(-3.5),
+            Amino::Gly => Some(-0.4),
+            Amino::His => Some(-3.2),
+            Amino::Ile => Some(4.5),
+            Amino::Leu => Some(3.8),
+            Amino::Lys => Some(-3.9),
+            Amino::Met => Some(1.9),
+            Amino::Phe => Some(2.8),
+            Amino::Pro => Some(-1.6),
+            Amino::Ser => Some(-0.8),
+            Amino::Thr => Some(-0.7),
+            Amino::Trp => Some(-0.9),
+            Amino::Tyr => Some(-1.3),
+            Amino::Val => Some(4.2),
+            _ => None,
+        }
+    }
+
+    // NOTE: The side chain's pKa, as EMBOSS has it, and the charge it
+    // carries on the charged side of that pKa: +1 for bases, -1 for acids.
+    pub fn side_chain_pka(&self) -> Option<(f64, i8)> {
+        match self {
+            Amino::Lys => Some((10.8, 1)),
+            Amino::Arg => Some((12.5, 1)),
+            Amino::His => Some((6.5, 1)),
+            Amino::Asp => Some((3.9, -1)),
+            Amino::Glu => Some((4.1, -1)),
+            Amino::Cys => Some((8.5, -1)),
+            Amino::Tyr => Some((10.1, -1)),
+            _ => None,
+        }
+    }
+
+    pub fn extinction(&self) -> u32 {
+        match self {
+            Amino::Trp => 5500,
+            Amino::Tyr => 1490,
+            _ => 0,
+        }
+    }
+}
+
+// NOTE: {} writes the one-letter code, {:#} the name, three letters but
+// for START and STOP, which protein_from_string reads back with Three or
+// Auto. START has no one-letter code, so it is always written by name.
+impl fmt::Display for Amino {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (f.alternate(), self.one_letter()) {
+            (false, Some(x)) => write!(f, "{}", x),
+            _ => write!(f, "{}", self.three_letter()),
+        }
+    }
 }
 
 pub type AminoCell = Monomer<Amino>;
 
+// How a protein string spells its residues.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Code {
+    One,
+    Three,
+    // Three if every three letter chunk is a name, otherwise One.
+    Auto,
+}
+
 pub struct AminoMorphisms {
     pub start: AminoCell,
     pub stop: AminoCell,
@@ -89,6 +441,12 @@ pub struct AminoMorphisms {
     pub trp: AminoCell,
     pub tyr: AminoCell,
     pub val: AminoCell,
+    pub sec: AminoCell,
+    pub pyl: AminoCell,
+    pub asx: AminoCell,
+    pub glx: AminoCell,
+    pub xle: AminoCell,
+    pub xaa: AminoCell,
 }
 
 impl AminoMorphisms {
@@ -116,6 +474,12 @@ impl AminoMorphisms {
             trp: Monomer::from_string(String::from("trp")).unwrap(),
             tyr: Monomer::from_string(String::from("tyr")).unwrap(),
             val: Monomer::from_string(String::from("val")).unwrap(),
+            sec: Monomer::from_string(String::from("sec")).unwrap(),
+            pyl: Monomer::from_string(String::from("pyl")).unwrap(),
+            asx: Monomer::from_string(String::from("asx")).unwrap(),
+            glx: Monomer::from_string(String::from("glx")).unwrap(),
+            xle: Monomer::from_string(String::from("xle")).unwrap(),
+            xaa: Monomer::from_string(String::from("xaa")).unwrap(),
         }
     }
 }
@@ -131,61 +495,326 @@ impl Cat<AminoCell, Strand<AminoCell>> for AminoCat {
         }
     }
 
-    fn monomer_from_string(&self, s: String) -> Option<AminoCell> {
-        match Amino::from_string(s) {
-            None => None,
-            Some(x) => match x {
-                Amino::START => Some(self.morphisms.start.clone()),
-                Amino::STOP => Some(self.morphisms.stop.clone()),
-                Amino::Ala => Some(self.morphisms.ala.clone()),
-                Amino::Arg => Some(self.morphisms.arg.clone()),
-                Amino::Asn => Some(self.morphisms.asn.clone()),
-                Amino::Asp => Some(self.morphisms.asp.clone()),
-                Amino::Cys => Some(self.morphisms.cys.clone()),
-                Amino::Gln => Some(self.morphisms.gln.clone()),
-                Amino::Glu => Some(self.morphisms.glu.clone()),
-                Amino::Gly => Some(self.morphisms.gly.clone()),
-                Amino::His => Some(self.morphisms.his.clone()),
-                Amino::Ile => Some(self.morphisms.ile.clone()),
-                Amino::Leu => Some(self.morphisms.leu.clone()),
-                Amino::Lys => Some(self.morphisms.lys.clone()),
-                Amino::Met => Some(self.morphisms.met.clone()),
-                Amino::Phe => Some(self.morphisms.phe.clone()),
-                Amino::Pro => Some(self.morphisms.pro.clone()),
-                Amino::Ser => Some(self.morphisms.ser.clone()),
-                Amino::Thr => Some(self.morphisms.thr.clone()),
-                Amino::Trp => Some(self.morphisms.trp.clone()),
-                Amino::Tyr => Some(self.morphisms.tyr.clone()),
-                Amino::Val => Some(self.morphisms.val.clone()),
-            },
-        }
+    fn monomer_from_string(&self, s: String) -> Result<AminoCell, ParseError> {
+        Amino::from_string(s).map(|x| self.cell(&x))
+    }
+
+    fn monomer_from_bytes(&self, b: &[u8]) -> Result<AminoCell, ParseError> {
+        Amino::from_bytes(b).map(|x| self.cell(&x))
     }
 
     // NOTE: Not in final form just to satisfy the interface.
     // Assumes no START or STOP codons, as they will be infered via RIBOSOME.
-    fn polymer_from_string(&self, s: String) -> Option<Strand<AminoCell>> {
+    fn polymer_from_string(&self, s: String) -> Result<Strand<AminoCell>, ParseError> {
         let mut x = Strand::<AminoCell>::new();
-        match s.len() % 3 == 0 {
-            false => None,
+        let len = s.chars().count();
+        match len.is_multiple_of(3) {
+            false => Err(ParseError::Length { len, width: 3 }),
             _ => {
-                let mut ok = true;
+                let mut i = 0;
                 let mut z = s.chars().peekable();
                 while z.peek().is_some() {
                     let chunk: String = z.by_ref().take(3).collect();
                     match self.monomer_from_string(chunk) {
-                        Some(y) => x.push(y),
-                        None => {
-                            ok = false;
-                            break;
-                        }
+                        Ok(y) => x.push(y),
+                        Err(e) => return Err(e.offset_by(i)),
                     }
+                    i += 3;
                 }
 
-                match ok {
-                    true => Some(x),
-                    _ => None,
-                }
+                Ok(x)
+            }
+        }
+    }
+}
+
+impl Intern for AminoCat {
+    type Value = Amino;
+    type Cell = AminoCell;
+
+    fn intern(&self, x: &Amino) -> AminoCell {
+        self.cell(x)
+    }
+}
+
+impl AminoCat {
+    // One residue per byte, as in protein FASTA, with '*' for STOP.
+    pub fn polymer_from_one_letter(&self, b: &[u8]) -> Result<Strand<AminoCell>, ParseError> {
+        let mut x = Strand::<AminoCell>::new();
+        for (i, c) in b.iter().enumerate() {
+            match Amino::from_one_letter(*c) {
+                Ok(y) => x.push(self.cell(&y)),
+                Err(e) => return Err(e.offset_by(i)),
+            }
+        }
+
+        Ok(x)
+    }
+
+    // Three letter names run together, as in "MetAlaSTOP". STOP and START
+    // are read whole where they stand, every other name is three letters. A
+    // name cut short at the end is a Monomer error, as any other bad name.
+    pub fn polymer_from_three_letter(&self, b: &[u8]) -> Result<Strand<AminoCell>, ParseError> {
+        let mut x = Strand::<AminoCell>::new();
+        let mut i = 0;
+        while i < b.len() {
+            let rest = b[i..b.len().min(i + 5)].to_ascii_uppercase();
+            let width = match (rest.starts_with(b"STOP"), rest.starts_with(b"START")) {
+                (true, _) => 4,
+                (_, true) => 5,
+                _ => 3,
+            };
+            match Amino::from_bytes(&b[i..b.len().min(i + width)]) {
+                Ok(y) => x.push(self.cell(&y)),
+                Err(e) => return Err(e.offset_by(i)),
+            }
+            i += width;
+        }
+
+        Ok(x)
+    }
+
+    // NOTE: Auto reads "MetAla" as Met Ala but "MKTAYIAK" as eight
+    // residues. A string that is both, such as "ALA", is read as three
+    // letter, so pass One or Three where it matters. Where neither parses,
+    // the error is Readings with the three-letter error, then the one-letter.
+    pub fn protein_from_string(
+        &self,
+        s: String,
+        code: Code,
+    ) -> Result<Strand<AminoCell>, ParseError> {
+        match code {
+            Code::One => self.polymer_from_one_letter(s.as_bytes()),
+            Code::Three => self.polymer_from_three_letter(s.as_bytes()),
+            Code::Auto => self
+                .polymer_from_three_letter(s.as_bytes())
+                .or_else(|three| {
+                    self.polymer_from_one_letter(s.as_bytes())
+                        .map_err(|one| ParseError::Readings(vec![three, one]))
+                }),
+        }
+    }
+
+    fn cell(&self, x: &Amino) -> AminoCell {
+        match x {
+            Amino::START => self.morphisms.start.clone(),
+            Amino::STOP => self.morphisms.stop.clone(),
+            Amino::Ala => self.morphisms.ala.clone(),
+            Amino::Arg => self.morphisms.arg.clone(),
+            Amino::Asn => self.morphisms.asn.clone(),
+            Amino::Asp => self.morphisms.asp.clone(),
+            Amino::Cys => self.morphisms.cys.clone(),
+            Amino::Gln => self.morphisms.gln.clone(),
+            Amino::Glu => self.morphisms.glu.clone(),
+            Amino::Gly => self.morphisms.gly.clone(),
+            Amino::His => self.morphisms.his.clone(),
+            Amino::Ile => self.morphisms.ile.clone(),
+            Amino::Leu => self.morphisms.leu.clone(),
+            Amino::Lys => self.morphisms.lys.clone(),
+            Amino::Met => self.morphisms.met.clone(),
+            Amino::Phe => self.morphisms.phe.clone(),
+            Amino::Pro => self.morphisms.pro.clone(),
+            Amino::Ser => self.morphisms.ser.clone(),
+            Amino::Thr => self.morphisms.thr.clone(),
+            Amino::Trp => self.morphisms.trp.clone(),
+            Amino::Tyr => self.morphisms.tyr.clone(),
+            Amino::Val => self.morphisms.val.clone(),
+            Amino::Sec => self.morphisms.sec.clone(),
+            Amino::Pyl => self.morphisms.pyl.clone(),
+            Amino::Asx => self.morphisms.asx.clone(),
+            Amino::Glx => self.morphisms.glx.clone(),
+            Amino::Xle => self.morphisms.xle.clone(),
+            Amino::Xaa => self.morphisms.xaa.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn protein_parse_errors() {
+        let c = AminoCat::new();
+
+        assert_eq!(
+            c.polymer_from_string(String::from("metala"))
+                .unwrap()
+                .contents,
+            vec![c.morphisms.met.clone(), c.morphisms.ala.clone()]
+        );
+        assert_eq!(
+            c.polymer_from_string(String::from("metal")).unwrap_err(),
+            ParseError::Length { len: 5, width: 3 }
+        );
+        assert_eq!(
+            c.polymer_from_string(String::from("metxyzala"))
+                .unwrap_err(),
+            ParseError::Monomer {
+                found: String::from("xyz"),
+                offset: 3,
+                alphabet: Amino::ALPHABET,
+            }
+        );
+        assert!(c.maybe_polymer_from_string(String::from("me")).is_none());
+    }
+
+    #[test]
+    fn protein_from_bytes() {
+        let c = AminoCat::new();
+
+        assert_eq!(Amino::from_bytes(b"Stop"), Ok(Amino::STOP));
+        assert_eq!(
+            c.polymer_from_bytes(b"METala").unwrap(),
+            c.polymer_from_string(String::from("metala")).unwrap()
+        );
+        assert_eq!(
+            c.polymer_from_bytes(b"metal").unwrap_err(),
+            ParseError::Length { len: 5, width: 3 }
+        );
+        assert_eq!(
+            c.polymer_from_bytes(b"metalaxyz").unwrap_err(),
+            ParseError::Monomer {
+                found: String::from("xyz"),
+                offset: 6,
+                alphabet: Amino::ALPHABET,
             }
+        );
+    }
+
+    #[test]
+    fn protein_one_letter() {
+        let c = AminoCat::new();
+
+        let mut p = c.polymer_from_string(String::from("metala")).unwrap();
+        p.push(c.morphisms.stop.clone());
+        assert_eq!(c.polymer_from_one_letter(b"Ma*").unwrap(), p);
+        assert_eq!(
+            c.polymer_from_one_letter(b"MA#A").unwrap_err(),
+            ParseError::Monomer {
+                found: String::from("#"),
+                offset: 2,
+                alphabet: Amino::ONE_LETTER,
+            }
+        );
+        assert_eq!(Amino::Trp.one_letter(), Some('W'));
+        assert_eq!(Amino::START.one_letter(), None);
+    }
+
+    #[test]
+    fn protein_code_detection() {
+        let c = AminoCat::new();
+        let one = c
+            .protein_from_string(String::from("MKTAYIAK"), Code::Auto)
+            .unwrap();
+
+        assert_eq!(one.len(), 8);
+        assert_eq!(one.get(1), Some(c.morphisms.lys.clone()));
+        assert_eq!(
+            c.protein_from_string(String::from("MetLysThr"), Code::Auto)
+                .unwrap(),
+            c.protein_from_string(String::from("MKT"), Code::One)
+                .unwrap()
+        );
+        assert_eq!(
+            c.protein_from_string(String::from("ALA"), Code::Auto)
+                .unwrap()
+                .len(),
+            1
+        );
+        assert_eq!(
+            c.protein_from_string(String::from("ALA"), Code::One)
+                .unwrap()
+                .len(),
+            3
+        );
+        assert_eq!(
+            c.protein_from_string(String::from("MK"), Code::Three)
+                .unwrap_err(),
+            ParseError::monomer(String::from("MK"), "amino acid (three-letter codes)")
+        );
+        assert_eq!(
+            c.protein_from_string(String::from("MetST"), Code::Three)
+                .unwrap_err(),
+            ParseError::monomer(String::from("ST"), "amino acid (three-letter codes)").offset_by(3)
+        );
+        assert_eq!(
+            c.protein_from_string(String::from("MetK1"), Code::Auto)
+                .unwrap_err(),
+            ParseError::Readings(vec![
+                ParseError::monomer(String::from("K1"), "amino acid (three-letter codes)")
+                    .offset_by(3),
+                ParseError::monomer(String::from("1"), "amino acid (one-letter codes)")
+                    .offset_by(4),
+            ])
+        );
+    }
+
+    #[test]
+    fn extended_alphabet() {
+        let c = AminoCat::new();
+        let p = c
+            .protein_from_string(String::from("UOBZJX"), Code::One)
+            .unwrap();
+
+        assert_eq!(
+            p,
+            c.protein_from_string(String::from("secpylasxglxxlexaa"), Code::Three)
+                .unwrap()
+        );
+        assert_eq!(p.get(5), Some(c.morphisms.xaa.clone()));
+        assert_eq!(Amino::Asx.members(), vec![Amino::Asn, Amino::Asp]);
+        assert_eq!(Amino::Xaa.members().len(), 20);
+        assert_eq!(Amino::Sec.members(), vec![Amino::Sec]);
+        assert!(Amino::Xle.is_ambiguous());
+        assert!(!Amino::Pyl.is_ambiguous());
+    }
+
+    #[test]
+    fn protein_display() {
+        let c = AminoCat::new();
+        let p = c
+            .protein_from_string(String::from("MKT*"), Code::One)
+            .unwrap();
+        let one: String = p.iter().map(|x| x.to_string()).collect();
+        let three: String = p.iter().map(|x| format!("{:#}", x)).collect();
+
+        assert_eq!(one, "MKT*");
+        assert_eq!(three, "MetLysThrSTOP");
+        assert_eq!(Amino::START.to_string(), "START");
+        assert_eq!(c.protein_from_string(three, Code::Auto).unwrap(), p);
+
+        let mut q = Strand::<AminoCell>::new();
+        q.push(c.morphisms.start.clone());
+        for x in p.iter() {
+            q.push(x);
         }
+        let named: String = q.iter().map(|x| format!("{:#}", x)).collect();
+        assert_eq!(named, "STARTMetLysThrSTOP");
+        assert_eq!(c.protein_from_string(named, Code::Three).unwrap(), q);
+        assert_eq!(
+            c.protein_from_string(String::from("MetSto"), Code::Three)
+                .unwrap_err(),
+            ParseError::Monomer {
+                found: String::from("Sto"),
+                offset: 3,
+                alphabet: "amino acid (three-letter codes)",
+            }
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn protein_serde_round_trip() {
+        let c = AminoCat::new();
+        let mut p = c.polymer_from_string(String::from("metala")).unwrap();
+        p.push(c.morphisms.stop.clone());
+
+        let json = serde_json::to_string(&p).unwrap();
+        assert_eq!(json, r#"["Met","Ala","STOP"]"#);
+
+        let mut de = serde_json::Deserializer::from_str(&json);
+        let q: Strand<AminoCell> = c.deserialize_polymer(&mut de).unwrap();
+        assert_eq!(q, p);
     }
 }
@@ -0,0 +1,296 @@
+use category::ICat;
+use format::Lines;
+pub use format::{FormatError, Sense};
+use monomer::IMono;
+use polymer::IPolymer;
+use std::io::BufRead;
+
+pub type BedError = FormatError;
+
+// NOTE: Positions are 0-based and half open, as in the file. Columns past
+// the first three are None (or empty) when the line does not have them.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Record {
+    pub chrom: String,
+    pub start: usize,
+    pub end: usize,
+    pub name: Option<String>,
+    pub score: Option<f64>,
+    pub sense: Sense,
+    pub thick: Option<(usize, usize)>,
+    pub rgb: Option<String>,
+    // BED12 (start, size) pairs, starts relative to the record's start.
+    pub blocks: Vec<(usize, usize)>,
+}
+
+impl Record {
+    // NOTE: BED12 blocks are joined in order, and a '-' record is reverse
+    // complemented, so the result reads 5' to 3' along the feature. None if
+    // the record runs off the end of seq.
+    pub fn extract<T, U, C>(&self, seq: &U, cat: &C) -> Option<U>
+    where
+        T: IMono,
+        U: IPolymer<T>,
+        C: ICat<T, U>,
+    {
+        let x = match self.blocks.is_empty() {
+            true => seq.subsequence(self.start..self.end)?,
+            _ => {
+                let mut x = U::new();
+                for (start, size) in self.blocks.iter() {
+                    let s = self.start + start;
+                    x.concat(&mut seq.subsequence(s..s + size)?);
+                }
+                x
+            }
+        };
+
+        match self.sense {
+            Sense::Reverse => Some(cat.inverse_p(&x)),
+            _ => Some(x),
+        }
+    }
+}
+
+fn list(s: &str) -> Option<Vec<usize>> {
+    s.trim_end_matches(',')
+        .split(',')
+        .map(|x| x.trim().parse().ok())
+        .collect()
+}
+
+fn record(l: &str, line: usize) -> Result<Record, BedError> {
+    let columns: Vec<&str> = l.split('\t').collect();
+    let err = |column: usize, expected: &'static str| BedError::Format {
+        line,
+        column,
+        expected,
+    };
+
+    if columns.len() < 3 {
+        return Err(err(
+            columns.len() + 1,
+            "at least three tab separated columns",
+        ));
+    }
+    if (10..12).contains(&columns.len()) || columns.len() > 12 {
+        return Err(err(columns.len().min(12), "all twelve BED12 columns"));
+    }
+
+    let start: usize = columns[1].parse().map_err(|_| err(2, "a start position"))?;
+    let end: usize = columns[2].parse().map_err(|_| err(3, "an end position"))?;
+    if end < start {
+        return Err(err(3, "start <= end"));
+    }
+
+    let name = columns.get(3).map(|x| x.to_string());
+    let score = match columns.get(4) {
+        None | Some(&".") => None,
+        Some(x) => Some(x.parse().map_err(|_| err(5, "a score or '.'"))?),
+    };
+
+    let sense = match columns.get(5) {
+        Some(&"+") => Sense::Forward,
+        Some(&"-") => Sense::Reverse,
+        None | Some(&".") => Sense::Unknown,
+        _ => return Err(err(6, "'+', '-' or '.'")),
+    };
+
+    let thick = match (columns.get(6), columns.get(7)) {
+        (Some(x), Some(y)) => Some((
+            x.parse().map_err(|_| err(7, "a thick start"))?,
+            y.parse().map_err(|_| err(8, "a thick end"))?,
+        )),
+        (Some(_), None) => return Err(err(8, "a thick end")),
+        _ => None,
+    };
+
+    let rgb = columns.get(8).map(|x| x.to_string());
+
+    let mut blocks = Vec::new();
+    if columns.len() == 12 {
+        let count: usize = columns[9].parse().map_err(|_| err(10, "a block count"))?;
+        let sizes = list(columns[10]).ok_or_else(|| err(11, "block sizes"))?;
+        let starts = list(columns[11]).ok_or_else(|| err(12, "block starts"))?;
+
+        if sizes.len() != count {
+            return Err(err(11, "a size for every block"));
+        }
+        if starts.len() != count {
+            return Err(err(12, "a start for every block"));
+        }
+        if starts
+            .iter()
+            .zip(sizes.iter())
+            .any(|(x, y)| start + x + y > end)
+        {
+            return Err(err(12, "blocks inside the record"));
+        }
+
+        blocks = starts.into_iter().zip(sizes).collect();
+    }
+
+    Ok(Record {
+        chrom: columns[0].to_string(),
+        start,
+        end,
+        name,
+        score,
+        sense,
+        thick,
+        rgb,
+        blocks,
+    })
+}
+
+// NOTE: Streams records, skipping blank, comment, track and browser lines.
+// Each record is a line of its own, so a bad one is an error and reading
+// goes on with the next, only an io error ends the stream.
+pub struct Reader<R> {
+    lines: Lines<R>,
+}
+
+impl<R: BufRead> Reader<R> {
+    pub fn new(inner: R) -> Self {
+        Reader {
+            lines: Lines::new(inner),
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for Reader<R> {
+    type Item = Result<Record, BedError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for x in self.lines.by_ref() {
+            let (n, l) = match x {
+                Ok(y) => y,
+                Err(e) => return Some(Err(e.into())),
+            };
+
+            let skip = l.trim().is_empty()
+                || l.starts_with('#')
+                || l.starts_with("track")
+                || l.starts_with("browser");
+            if skip {
+                continue;
+            }
+
+            return Some(record(&l, n));
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use category::Cat;
+    use dna::DNACat;
+
+    //                    0         1         2
+    //                    0123456789012345678901234567
+    const GENOME: &str = "ccccATGcccAAAtttGTTTAAgggggg";
+
+    const INPUT: &str = "\
+track name=test
+# comment
+chr1\t4\t7
+chr1\t4\t7\tatg\t0\t-
+
+chr1\t4\t22\ttx\t900\t+\t4\t22\t255,0,0\t3\t3,3,6,\t0,6,12,
+";
+
+    fn read(input: &str) -> Vec<Result<Record, BedError>> {
+        Reader::new(input.as_bytes()).collect()
+    }
+
+    #[test]
+    fn read_records() {
+        let x: Vec<Record> = read(INPUT).into_iter().collect::<Result<_, _>>().unwrap();
+
+        assert_eq!(x.len(), 3);
+        assert_eq!((x[0].start, x[0].end, x[0].sense), (4, 7, Sense::Unknown));
+        assert_eq!(x[0].name, None);
+        assert_eq!(x[1].name, Some(String::from("atg")));
+        assert_eq!(x[1].sense, Sense::Reverse);
+        assert_eq!(x[2].score, Some(900.0));
+        assert_eq!(x[2].thick, Some((4, 22)));
+        assert_eq!(x[2].blocks, vec![(0, 3), (6, 3), (12, 6)]);
+    }
+
+    #[test]
+    fn read_errors() {
+        match &read("chr1\t4\n")[..] {
+            [Err(BedError::Format {
+                line: 1, column: 3, ..
+            })] => {}
+            y => panic!("expected a format error, got {:?}", y),
+        }
+        match &read("chr1\t7\t4\n")[..] {
+            [Err(BedError::Format {
+                line: 1, column: 3, ..
+            })] => {}
+            y => panic!("expected a format error, got {:?}", y),
+        }
+        match &read("#\nchr1\t4\t7\tx\t0\t*\n")[..] {
+            [Err(BedError::Format {
+                line: 2, column: 6, ..
+            })] => {}
+            y => panic!("expected a format error, got {:?}", y),
+        }
+        match &read("chr1\t0\t10\tx\t0\t+\t0\t10\t0\t2\t3,3\t0\n")[..] {
+            [Err(BedError::Format {
+                line: 1,
+                column: 12,
+                ..
+            })] => {}
+            y => panic!("expected a format error, got {:?}", y),
+        }
+        match &read("chr1\t0\t10\tx\t0\t+\t0\t10\t0\t1\t3\t8\n")[..] {
+            [Err(BedError::Format {
+                line: 1,
+                column: 12,
+                ..
+            })] => {}
+            y => panic!("expected a format error, got {:?}", y),
+        }
+
+        let x = read("chr1\t7\t4\nchr1\t4\t7\n");
+        assert_eq!(x.len(), 2);
+        assert!(x[0].is_err());
+        assert_eq!(x[1].as_ref().unwrap().start, 4);
+    }
+
+    #[test]
+    fn extract_regions() {
+        let c = DNACat::new();
+        let genome = c.polymer_from_string(String::from(GENOME)).unwrap();
+        let x: Vec<Record> = read(INPUT).into_iter().collect::<Result<_, _>>().unwrap();
+
+        assert_eq!(
+            x[0].extract(&genome, &c).unwrap(),
+            c.polymer_from_string(String::from("atg")).unwrap()
+        );
+        assert_eq!(
+            x[1].extract(&genome, &c).unwrap(),
+            c.polymer_from_string(String::from("cat")).unwrap()
+        );
+        assert_eq!(
+            x[2].extract(&genome, &c).unwrap(),
+            c.polymer_from_string(String::from("atgaaagtttaa")).unwrap()
+        );
+
+        let mut minus = x[2].clone();
+        minus.sense = Sense::Reverse;
+        assert_eq!(
+            minus.extract(&genome, &c).unwrap(),
+            c.polymer_from_string(String::from("ttaaactttcat")).unwrap()
+        );
+
+        let mut off_end = x[0].clone();
+        off_end.end = 40;
+        assert_eq!(off_end.extract(&genome, &c), None);
+    }
+}
@@ -1,14 +1,51 @@
-use monomer::{IMono, Mono, NucleicAcid};
+use monomer::{IMono, Mono, NucleicAcid, ParseError};
 use polymer::{IPolymer, Polymer};
 
+#[cfg(feature = "serde")]
+use std::marker::PhantomData;
+
 pub trait Cat<T, U>
 where
     T: Mono,
     U: Polymer<T>,
 {
     fn new() -> Self;
-    fn monomer_from_string(&self, c: String) -> Option<T>;
-    fn polymer_from_string(&self, s: String) -> Option<U>;
+    fn monomer_from_string(&self, c: String) -> Result<T, ParseError>;
+    fn polymer_from_string(&self, s: String) -> Result<U, ParseError>;
+
+    fn maybe_monomer_from_string(&self, c: String) -> Option<T> {
+        self.monomer_from_string(c).ok()
+    }
+
+    fn maybe_polymer_from_string(&self, s: String) -> Option<U> {
+        self.polymer_from_string(s).ok()
+    }
+
+    // NOTE: Falls back to monomer_from_string, Cats should override this
+    // to hand out their cells straight from a lookup.
+    fn monomer_from_bytes(&self, b: &[u8]) -> Result<T, ParseError> {
+        self.monomer_from_string(String::from_utf8_lossy(b).into_owned())
+    }
+
+    fn polymer_from_bytes(&self, b: &[u8]) -> Result<U, ParseError> {
+        if !b.len().is_multiple_of(T::WIDTH) {
+            return Err(ParseError::Length {
+                len: b.len(),
+                width: T::WIDTH,
+            });
+        }
+
+        let mut x = U::new();
+
+        for (i, c) in b.chunks(T::WIDTH).enumerate() {
+            x.push(
+                self.monomer_from_bytes(c)
+                    .map_err(|e| e.offset_by(i * T::WIDTH))?,
+            );
+        }
+
+        Ok(x)
+    }
 }
 
 pub trait ICat<T, U>: Cat<T, U>
@@ -26,4 +63,79 @@ where
     U: IPolymer<T>,
 {
     fn gc_content(u: U) -> (u64, u64);
-}
\ No newline at end of file
+}
+
+// NOTE: Hands out the Cat's shared cell for a plain monomer value, which is
+// how serialized monomers find their way back into the Cat.
+pub trait Intern {
+    type Value;
+    type Cell: Mono;
+
+    fn intern(&self, x: &Self::Value) -> Self::Cell;
+
+    #[cfg(feature = "serde")]
+    fn deserialize_monomer<'de, D>(&self, d: D) -> Result<Self::Cell, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+        Self::Value: serde::Deserialize<'de>,
+    {
+        <Self::Value as serde::Deserialize>::deserialize(d).map(|x| self.intern(&x))
+    }
+
+    #[cfg(feature = "serde")]
+    fn deserialize_polymer<'de, U, D>(&self, d: D) -> Result<U, D::Error>
+    where
+        U: Polymer<Self::Cell>,
+        D: serde::Deserializer<'de>,
+        Self::Value: serde::Deserialize<'de>,
+    {
+        let mut x = U::new();
+        for y in <Vec<Self::Value> as serde::Deserialize>::deserialize(d)? {
+            x.push(self.intern(&y));
+        }
+
+        Ok(x)
+    }
+}
+
+// NOTE: For cells nested inside other types, where serde wants a seed
+// rather than a method call.
+#[cfg(feature = "serde")]
+pub struct MonomerSeed<'a, C>(pub &'a C);
+
+#[cfg(feature = "serde")]
+impl<'de, 'a, C> serde::de::DeserializeSeed<'de> for MonomerSeed<'a, C>
+where
+    C: Intern,
+    C::Value: serde::Deserialize<'de>,
+{
+    type Value = C::Cell;
+
+    fn deserialize<D: serde::Deserializer<'de>>(self, d: D) -> Result<C::Cell, D::Error> {
+        self.0.deserialize_monomer(d)
+    }
+}
+
+#[cfg(feature = "serde")]
+pub struct PolymerSeed<'a, C, U>(&'a C, PhantomData<U>);
+
+#[cfg(feature = "serde")]
+impl<'a, C, U> PolymerSeed<'a, C, U> {
+    pub fn new(c: &'a C) -> Self {
+        PolymerSeed(c, PhantomData)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, 'a, C, U> serde::de::DeserializeSeed<'de> for PolymerSeed<'a, C, U>
+where
+    C: Intern,
+    C::Value: serde::Deserialize<'de>,
+    U: Polymer<C::Cell>,
+{
+    type Value = U;
+
+    fn deserialize<D: serde::Deserializer<'de>>(self, d: D) -> Result<U, D::Error> {
+        self.0.deserialize_polymer(d)
+    }
+}
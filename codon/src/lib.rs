@@ -1,10 +1,16 @@
 use rna::{RNA, RNACell};
 use amino::Amino;
-use polymer::Strand;
+use polymer::{Polymer, Strand};
+
+#[cfg(feature = "serde")]
+use category::Intern;
+#[cfg(feature = "serde")]
+use rna::RNACat;
 
 type RNACodon = (RNACell, RNACell, RNACell);
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Codon {
     pub amino: Amino,
     pub rna: RNACodon,
@@ -27,127 +33,181 @@ impl Codon {
         }
     }
 
-    pub fn from_strand(s: &Strand<RNACell>) -> Option<Codon> {
-        let mut x = None;
-        let y = Self::tuple_from_strand(&s);
-        if s.contents.len() == 3 {
-            x = Some(Codon {
-                amino: Self::to_amino_encoding(&y),
-                rna: y,
-            });
+    pub fn from_strand(s: &Strand<RNACell>, code: &GeneticCode) -> Option<Codon> {
+        match s.len() == 3 {
+            true => {
+                let y = Self::tuple_from_strand(s);
+                Some(Codon {
+                    amino: Self::to_amino_encoding(&y, code),
+                    rna: y,
+                })
+            }
+            _ => None,
+        }
+    }
+
+    // NOTE: Reads a serialized Codon back, taking its cells from cat.
+    #[cfg(feature = "serde")]
+    pub fn deserialize_with<'de, D>(cat: &RNACat, d: D) -> Result<Codon, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        #[derive(serde::Deserialize)]
+        #[serde(rename = "Codon")]
+        struct Plain {
+            amino: Amino,
+            rna: (RNA, RNA, RNA),
         }
-        x
+
+        let x = <Plain as serde::Deserialize>::deserialize(d)?;
+        let (fst, snd, thd) = x.rna;
+        Ok(Codon {
+            amino: x.amino,
+            rna: (cat.intern(&fst), cat.intern(&snd), cat.intern(&thd)),
+        })
     }
 
     fn tuple_from_strand(s: &Strand<RNACell>) -> (RNACell, RNACell, RNACell) {
-        (
-            s.contents[0].clone(),
-            s.contents[1].clone(),
-            s.contents[2].clone(),
-        )
+        (s.get(0).unwrap(), s.get(1).unwrap(), s.get(2).unwrap())
     }
 
-    // NOTE: Ignoring the AUG => START command. Will use seperate function for this.
-    pub fn to_amino_encoding(r: &RNACodon) -> Amino {
-        //
-        let (fst, snd, thd) = r;
+    // NOTE: Ignoring the AUG => START command; see GeneticCode::is_start.
+    pub fn to_amino_encoding(r: &RNACodon, code: &GeneticCode) -> Amino {
+        code.translate(r)
+    }
+}
 
-        let x = fst.read();
-        let y = snd.read();
-        let z = thd.read();
+// NOTE: The NCBI translation tables, as printed in gc.prt. Each has one
+// residue and one start flag per codon, with the bases ordered U, C, A, G
+// and the first base varying slowest.
+// Reference: https://www.ncbi.nlm.nih.gov/Taxonomy/Utils/wprintgc.cgi
+// Tables 27, 28 and 31 read a codon that only stops in context as its
+// residue, as NCBI does.
+#[rustfmt::skip]
+const TABLES: [(u8, &str, &[u8; 64], &[u8; 64]); 27] = [
+    (1, "Standard", b"FFLLSSSSYY**CC*WLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG", b"---M---------------M---------------M----------------------------"),
+    (2, "Vertebrate Mitochondrial", b"FFLLSSSSYY**CCWWLLLLPPPPHHQQRRRRIIMMTTTTNNKKSS**VVVVAAAADDEEGGGG", b"--------------------------------MMMM---------------M------------"),
+    (3, "Yeast Mitochondrial", b"FFLLSSSSYY**CCWWTTTTPPPPHHQQRRRRIIMMTTTTNNKKSSRRVVVVAAAADDEEGGGG", b"----------------------------------MM---------------M------------"),
+    (4, "Mold, Protozoan, and Coelenterate Mitochondrial and Mycoplasma/Spiroplasma", b"FFLLSSSSYY**CCWWLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG", b"--MM---------------M------------MMMM---------------M------------"),
+    (5, "Invertebrate Mitochondrial", b"FFLLSSSSYY**CCWWLLLLPPPPHHQQRRRRIIMMTTTTNNKKSSSSVVVVAAAADDEEGGGG", b"---M----------------------------MMMM---------------M------------"),
+    (6, "Ciliate, Dasycladacean and Hexamita Nuclear", b"FFLLSSSSYYQQCC*WLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG", b"-----------------------------------M----------------------------"),
+    (9, "Echinoderm and Flatworm Mitochondrial", b"FFLLSSSSYY**CCWWLLLLPPPPHHQQRRRRIIIMTTTTNNNKSSSSVVVVAAAADDEEGGGG", b"-----------------------------------M---------------M------------"),
+    (10, "Euplotid Nuclear", b"FFLLSSSSYY**CCCWLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG", b"-----------------------------------M----------------------------"),
+    (11, "Bacterial, Archaeal and Plant Plastid", b"FFLLSSSSYY**CC*WLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG", b"---M---------------M------------MMMM---------------M------------"),
+    (12, "Alternative Yeast Nuclear", b"FFLLSSSSYY**CC*WLLLSPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG", b"-------------------M---------------M----------------------------"),
+    (13, "Ascidian Mitochondrial", b"FFLLSSSSYY**CCWWLLLLPPPPHHQQRRRRIIMMTTTTNNKKSSGGVVVVAAAADDEEGGGG", b"---M------------------------------MM---------------M------------"),
+    (14, "Alternative Flatworm Mitochondrial", b"FFLLSSSSYYY*CCWWLLLLPPPPHHQQRRRRIIIMTTTTNNNKSSSSVVVVAAAADDEEGGGG", b"-----------------------------------M----------------------------"),
+    (15, "Blepharisma Nuclear", b"FFLLSSSSYY*QCC*WLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG", b"-----------------------------------M----------------------------"),
+    (16, "Chlorophycean Mitochondrial", b"FFLLSSSSYY*LCC*WLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG", b"-----------------------------------M----------------------------"),
+    (21, "Trematode Mitochondrial", b"FFLLSSSSYY**CCWWLLLLPPPPHHQQRRRRIIMMTTTTNNNKSSSSVVVVAAAADDEEGGGG", b"-----------------------------------M---------------M------------"),
+    (22, "Scenedesmus obliquus Mitochondrial", b"FFLLSS*SYY*LCC*WLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG", b"-----------------------------------M----------------------------"),
+    (23, "Thraustochytrium Mitochondrial", b"FF*LSSSSYY**CC*WLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG", b"--------------------------------M--M---------------M------------"),
+    (24, "Rhabdopleuridae Mitochondrial", b"FFLLSSSSYY**CCWWLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSSKVVVVAAAADDEEGGGG", b"---M---------------M---------------M---------------M------------"),
+    (25, "Candidate Division SR1 and Gracilibacteria", b"FFLLSSSSYY**CCGWLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG", b"---M-------------------------------M---------------M------------"),
+    (26, "Pachysolen tannophilus Nuclear", b"FFLLSSSSYY**CC*WLLLAPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG", b"-------------------M---------------M----------------------------"),
+    (27, "Karyorelict Nuclear", b"FFLLSSSSYYQQCCWWLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG", b"-----------------------------------M----------------------------"),
+    (28, "Condylostoma Nuclear", b"FFLLSSSSYYQQCCWWLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG", b"-----------------------------------M----------------------------"),
+    (29, "Mesodinium Nuclear", b"FFLLSSSSYYYYCC*WLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG", b"-----------------------------------M----------------------------"),
+    (30, "Peritrich Nuclear", b"FFLLSSSSYYEECC*WLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG", b"-----------------------------------M----------------------------"),
+    (31, "Blastocrithidia Nuclear", b"FFLLSSSSYYEECCWWLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG", b"-----------------------------------M----------------------------"),
+    (32, "Balanophoraceae Plastid", b"FFLLSSSSYY*WCC*WLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG", b"---M---------------M------------MMMM---------------M------------"),
+    (33, "Cephalodiscidae Mitochondrial", b"FFLLSSSSYYY*CCWWLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSSKVVVVAAAADDEEGGGG", b"---M---------------M---------------M---------------M------------"),
+];
 
-        let plain_codon = (
-            x.as_ref().unwrap(),
-            y.as_ref().unwrap(),
-            z.as_ref().unwrap(),
-        );
+const BASES: [RNA; 4] = [RNA::U, RNA::C, RNA::A, RNA::G];
 
-        match plain_codon {
-            // Reference: https://cnx.org/contents/GFy_h8cu@9.87:QEibhJMi@8/The-Genetic-Code
-            // Square 1:
-            (&RNA::U, &RNA::U, &RNA::A) | (&RNA::U, &RNA::U, &RNA::G) => Amino::Leu,
-            (&RNA::U, &RNA::U, &RNA::U) | (&RNA::U, &RNA::U, &RNA::C) => Amino::Phe,
-
-            // Square 2:
-            (&RNA::U, &RNA::C, &RNA::U)
-            | (&RNA::U, &RNA::C, &RNA::C)
-            | (&RNA::U, &RNA::C, &RNA::A)
-            | (&RNA::U, &RNA::C, &RNA::G) => Amino::Ser,
-
-            // Square 3:
-            (&RNA::U, &RNA::A, &RNA::U) | (&RNA::U, &RNA::A, &RNA::C) => Amino::Tyr,
-            (&RNA::U, &RNA::A, &RNA::A) | (&RNA::U, &RNA::A, &RNA::G) => Amino::STOP,
-
-            // Square 4:
-            (&RNA::U, &RNA::G, &RNA::U) | (&RNA::U, &RNA::G, &RNA::C) => Amino::Cys,
-            (&RNA::U, &RNA::G, &RNA::A) => Amino::STOP,
-            (&RNA::U, &RNA::G, &RNA::G) => Amino::Trp,
-
-            // Square 5:
-            (&RNA::C, &RNA::U, &RNA::U)
-            | (&RNA::C, &RNA::U, &RNA::C)
-            | (&RNA::C, &RNA::U, &RNA::A)
-            | (&RNA::C, &RNA::U, &RNA::G) => Amino::Leu,
-
-            // Square 6:
-            (&RNA::C, &RNA::C, &RNA::U)
-            | (&RNA::C, &RNA::C, &RNA::C)
-            | (&RNA::C, &RNA::C, &RNA::A)
-            | (&RNA::C, &RNA::C, &RNA::G) => Amino::Pro,
-
-            // Square 7:
-            (&RNA::C, &RNA::A, &RNA::U) | (&RNA::C, &RNA::A, &RNA::C) => Amino::His,
-            (&RNA::C, &RNA::A, &RNA::A) | (&RNA::C, &RNA::A, &RNA::G) => Amino::Gln,
-
-            // Square 8:
-            (&RNA::C, &RNA::G, &RNA::U)
-            | (&RNA::C, &RNA::G, &RNA::C)
-            | (&RNA::C, &RNA::G, &RNA::A)
-            | (&RNA::C, &RNA::G, &RNA::G) => Amino::Arg,
-
-            // Square 9:
-            (&RNA::A, &RNA::U, &RNA::U)
-            | (&RNA::A, &RNA::U, &RNA::C)
-            | (&RNA::A, &RNA::U, &RNA::A) => Amino::Ile,
-            // NOTE: Not start, assumed start has been dealt with
-            (&RNA::A, &RNA::U, &RNA::G) => Amino::Met,
-
-            // Square 10:
-            (&RNA::A, &RNA::C, &RNA::U)
-            | (&RNA::A, &RNA::C, &RNA::C)
-            | (&RNA::A, &RNA::C, &RNA::A)
-            | (&RNA::A, &RNA::C, &RNA::G) => Amino::Thr,
-
-            // Square 11:
-            (&RNA::A, &RNA::A, &RNA::U) | (&RNA::A, &RNA::A, &RNA::C) => Amino::Asn,
-            (&RNA::A, &RNA::A, &RNA::A) | (&RNA::A, &RNA::A, &RNA::G) => Amino::Lys,
-
-            // Square 12:
-            (&RNA::A, &RNA::G, &RNA::U) | (&RNA::A, &RNA::G, &RNA::C) => Amino::Ser,
-            (&RNA::A, &RNA::G, &RNA::A) | (&RNA::A, &RNA::G, &RNA::G) => Amino::Arg,
-
-            // Square 13:
-            (&RNA::G, &RNA::U, &RNA::U)
-            | (&RNA::G, &RNA::U, &RNA::C)
-            | (&RNA::G, &RNA::U, &RNA::A)
-            | (&RNA::G, &RNA::U, &RNA::G) => Amino::Val,
-
-            // Square 14:
-            (&RNA::G, &RNA::C, &RNA::U)
-            | (&RNA::G, &RNA::C, &RNA::C)
-            | (&RNA::G, &RNA::C, &RNA::A)
-            | (&RNA::G, &RNA::C, &RNA::G) => Amino::Ala,
-
-            // Square 15:
-            (&RNA::G, &RNA::A, &RNA::U) | (&RNA::G, &RNA::A, &RNA::C) => Amino::Asp,
-            (&RNA::G, &RNA::A, &RNA::A) | (&RNA::G, &RNA::A, &RNA::G) => Amino::Glu,
-
-            // Square 16:
-            (&RNA::G, &RNA::G, &RNA::U)
-            | (&RNA::G, &RNA::G, &RNA::C)
-            | (&RNA::G, &RNA::G, &RNA::A)
-            | (&RNA::G, &RNA::G, &RNA::G) => Amino::Gly,
-        }
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GeneticCode {
+    id: u8,
+    name: &'static str,
+    aminos: &'static [u8; 64],
+    starts: &'static [u8; 64],
+}
+
+impl Default for GeneticCode {
+    fn default() -> Self {
+        Self::standard()
+    }
+}
+
+impl GeneticCode {
+    // NCBI's transl_table number: 1 to 33, less the retired 7, 8 and 17-20.
+    pub fn new(id: u8) -> Option<GeneticCode> {
+        TABLES
+            .iter()
+            .find(|x| x.0 == id)
+            .map(|&(id, name, aminos, starts)| GeneticCode {
+                id,
+                name,
+                aminos,
+                starts,
+            })
+    }
+
+    pub fn standard() -> GeneticCode {
+        Self::new(1).unwrap()
+    }
+
+    pub fn all() -> Vec<GeneticCode> {
+        TABLES.iter().filter_map(|x| Self::new(x.0)).collect()
+    }
+
+    pub fn id(&self) -> u8 {
+        self.id
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    pub fn translate(&self, r: &RNACodon) -> Amino {
+        self.amino(&Self::plain(r))
+    }
+
+    pub fn is_start(&self, r: &RNACodon) -> bool {
+        self.starts[Self::index(&Self::plain(r))] == b'M'
+    }
+
+    pub fn amino(&self, c: &(RNA, RNA, RNA)) -> Amino {
+        // NOTE: Every letter in TABLES is a one-letter code.
+        Amino::from_one_letter(self.aminos[Self::index(c)]).unwrap()
+    }
+
+    // Every codon this table reads as a, in table order.
+    pub fn codons(&self, a: &Amino) -> Vec<(RNA, RNA, RNA)> {
+        (0..64)
+            .map(Self::codon)
+            .filter(|c| &self.amino(c) == a)
+            .collect()
+    }
+
+    pub fn starts(&self) -> Vec<(RNA, RNA, RNA)> {
+        (0..64)
+            .filter(|&i| self.starts[i] == b'M')
+            .map(Self::codon)
+            .collect()
+    }
+
+    fn plain(r: &RNACodon) -> (RNA, RNA, RNA) {
+        let (fst, snd, thd) = r;
+        (
+            fst.read().as_ref().unwrap().clone(),
+            snd.read().as_ref().unwrap().clone(),
+            thd.read().as_ref().unwrap().clone(),
+        )
+    }
+
+    fn index(c: &(RNA, RNA, RNA)) -> usize {
+        let base = |x: &RNA| BASES.iter().position(|y| y == x).unwrap();
+        16 * base(&c.0) + 4 * base(&c.1) + base(&c.2)
+    }
+
+    fn codon(i: usize) -> (RNA, RNA, RNA) {
+        (
+            BASES[i / 16].clone(),
+            BASES[i / 4 % 4].clone(),
+            BASES[i % 4].clone(),
+        )
     }
 }
 
@@ -160,7 +220,7 @@ mod tests {
     fn codon_from_strand() {
         let cat = RNACat::new();
         let strand = cat.polymer_from_string(String::from("aug")).unwrap();
-        let codon = Codon::from_strand(&strand).unwrap();
+        let codon = Codon::from_strand(&strand, &GeneticCode::standard()).unwrap();
         let test = (
             cat.monomer_from_string(String::from("a")).unwrap(),
             cat.monomer_from_string(String::from("u")).unwrap(),
@@ -174,7 +234,7 @@ mod tests {
         // TODO: MORE RIGOROUS ACC. TESTS.
         let cat = RNACat::new();
         let strand = cat.polymer_from_string(String::from("aug")).unwrap();
-        let codon = Codon::from_strand(&strand).unwrap();
+        let codon = Codon::from_strand(&strand, &GeneticCode::standard()).unwrap();
 
         let amino = Amino::Met;
 
@@ -182,4 +242,63 @@ mod tests {
         assert_eq!((strand.contents[0].clone(), strand.contents[1].clone(), strand.contents[2].clone()), codon.rna);
 
     }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn codon_serde_round_trip() {
+        let cat = RNACat::new();
+        let strand = cat.polymer_from_string(String::from("uaa")).unwrap();
+        let codon = Codon::from_strand(&strand, &GeneticCode::standard()).unwrap();
+
+        let json = serde_json::to_string(&codon).unwrap();
+        assert_eq!(json, r#"{"amino":"STOP","rna":["U","A","A"]}"#);
+
+        let mut de = serde_json::Deserializer::from_str(&json);
+        let back = Codon::deserialize_with(&cat, &mut de).unwrap();
+        assert!(back.deep_equal(&codon));
+    }
+
+    #[test]
+    fn genetic_code_tables() {
+        assert_eq!(GeneticCode::all().len(), 27);
+        assert!(GeneticCode::new(7).is_none());
+        assert!(GeneticCode::new(34).is_none());
+
+        let std = GeneticCode::standard();
+        assert_eq!(std.codons(&Amino::STOP).len(), 3);
+        assert_eq!(std.codons(&Amino::Arg).len(), 6);
+        assert_eq!(std.codons(&Amino::Sec).len(), 0);
+        assert_eq!(
+            std.starts(),
+            vec![
+                (RNA::U, RNA::U, RNA::G),
+                (RNA::C, RNA::U, RNA::G),
+                (RNA::A, RNA::U, RNA::G)
+            ]
+        );
+
+        let mt = GeneticCode::new(2).unwrap();
+        assert_eq!(mt.name(), "Vertebrate Mitochondrial");
+        assert_eq!(mt.amino(&(RNA::U, RNA::G, RNA::A)), Amino::Trp);
+        assert_eq!(mt.amino(&(RNA::A, RNA::G, RNA::A)), Amino::STOP);
+        assert_eq!(mt.amino(&(RNA::A, RNA::U, RNA::A)), Amino::Met);
+        assert_eq!(mt.codons(&Amino::STOP).len(), 4);
+    }
+
+    #[test]
+    fn codon_with_code() {
+        let cat = RNACat::new();
+        let strand = cat.polymer_from_string(String::from("uga")).unwrap();
+        let std = Codon::from_strand(&strand, &GeneticCode::standard()).unwrap();
+        let mt = Codon::from_strand(&strand, &GeneticCode::new(2).unwrap()).unwrap();
+        assert_eq!(std.amino, Amino::STOP);
+        assert_eq!(mt.amino, Amino::Trp);
+
+        let bacterial = GeneticCode::new(11).unwrap();
+        let gug = cat.polymer_from_string(String::from("gug")).unwrap();
+        let gug = Codon::from_strand(&gug, &bacterial).unwrap();
+        assert!(bacterial.is_start(&gug.rna));
+        assert!(!GeneticCode::standard().is_start(&gug.rna));
+        assert_eq!(gug.amino, Amino::Val);
+    }
 }
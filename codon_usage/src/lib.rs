@@ -0,0 +1,599 @@
+use amino::Amino;
+use category::Intern;
+pub use format::FormatError;
+use format::Lines;
+use polymer::{Polymer, Strand};
+use ribosome::Ribosome;
+use rna::{RNACell, RNA};
+
+use std::io::{self, BufRead, Write};
+
+// NOTE: Codons are indexed 16 * first + 4 * second + third, with the bases
+// ordered U, C, A, G, the same order as the NCBI and Kazusa tables.
+const BASES: [RNA; 4] = [RNA::U, RNA::C, RNA::A, RNA::G];
+
+// Sharp and Li (1987) count a codon never seen in the reference as half of
+// one, so that a single rare codon does not zero a gene's CAI.
+pub const CAI_PSEUDOCOUNT: f64 = 0.5;
+
+pub type UsageError = FormatError;
+
+// One codon's line of a usage table. frequency is the codon's share of its
+// residue's codons and rscu its count over the even share; both are None
+// when the residue was never seen.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Row {
+    pub codon: (RNA, RNA, RNA),
+    pub amino: Amino,
+    pub count: u64,
+    pub frequency: Option<f64>,
+    pub per_thousand: f64,
+    pub rscu: Option<f64>,
+}
+
+// NOTE: Synonymous codons are grouped by what the Ribosome, and so its
+// GeneticCode, reads them as. STOP is a family like any residue.
+#[derive(Debug, Clone)]
+pub struct CodonUsage {
+    aminos: Vec<Amino>,
+    counts: [u64; 64],
+}
+
+impl CodonUsage {
+    pub fn new(ribo: &Ribosome) -> CodonUsage {
+        let aminos = (0..64)
+            .map(|i| {
+                let (x, y, z) = Self::codon(i);
+                let c = (
+                    ribo.rna_c.intern(&x),
+                    ribo.rna_c.intern(&y),
+                    ribo.rna_c.intern(&z),
+                );
+                let a = ribo.codon_to_amino(&c);
+                let b = a.read();
+                b.as_ref().unwrap().clone()
+            })
+            .collect();
+
+        CodonUsage {
+            aminos,
+            counts: [0; 64],
+        }
+    }
+
+    pub fn from_genes(ribo: &Ribosome, genes: &[Strand<RNACell>]) -> CodonUsage {
+        let mut x = Self::new(ribo);
+        for g in genes.iter() {
+            x.add(g);
+        }
+
+        x
+    }
+
+    // NOTE: gene is read in frame from its first base; a trailing partial
+    // codon is ignored.
+    pub fn add(&mut self, gene: &Strand<RNACell>) {
+        for i in Self::indices(gene) {
+            self.counts[i] += 1;
+        }
+    }
+
+    pub fn count(&self, c: &(RNA, RNA, RNA)) -> u64 {
+        self.counts[Self::index(c)]
+    }
+
+    pub fn total(&self) -> u64 {
+        self.counts.iter().sum()
+    }
+
+    pub fn amino(&self, c: &(RNA, RNA, RNA)) -> &Amino {
+        &self.aminos[Self::index(c)]
+    }
+
+    // Every codon read as a, in table order.
+    pub fn synonyms(&self, a: &Amino) -> Vec<(RNA, RNA, RNA)> {
+        self.family(a).into_iter().map(Self::codon).collect()
+    }
+
+    pub fn frequency(&self, c: &(RNA, RNA, RNA)) -> Option<f64> {
+        let i = Self::index(c);
+        match self.family_total(&self.aminos[i]) {
+            0 => None,
+            n => Some(self.counts[i] as f64 / n as f64),
+        }
+    }
+
+    pub fn per_thousand(&self, c: &(RNA, RNA, RNA)) -> f64 {
+        match self.total() {
+            0 => 0.0,
+            n => 1000.0 * self.count(c) as f64 / n as f64,
+        }
+    }
+
+    pub fn rscu(&self, c: &(RNA, RNA, RNA)) -> Option<f64> {
+        let k = self.family(self.amino(c)).len() as f64;
+        self.frequency(c).map(|x| x * k)
+    }
+
+    pub fn table(&self) -> Vec<Row> {
+        (0..64)
+            .map(|i| {
+                let c = Self::codon(i);
+                Row {
+                    amino: self.aminos[i].clone(),
+                    count: self.counts[i],
+                    frequency: self.frequency(&c),
+                    per_thousand: self.per_thousand(&c),
+                    rscu: self.rscu(&c),
+                    codon: c,
+                }
+            })
+            .collect()
+    }
+
+    // NOTE: Reads the Kazusa / CoCoPUTs layout, four codons to a line:
+    //
+    //   UUU 17.6(714298)  UCU 15.2(618711)  UAU 12.2(495699)  UGU 10.6(430311)
+    //
+    // Only the count in parentheses is kept; anything between it and the
+    // codon (a residue letter, a fraction, the per thousand) is skipped, so
+    // both of Kazusa's formats read. T is taken for U. Lines that do not
+    // start with a codon are headers and are skipped, and codons not in the
+    // file count zero.
+    pub fn read<R: BufRead>(ribo: &Ribosome, r: R) -> Result<CodonUsage, UsageError> {
+        let mut x = Self::new(ribo);
+        let mut seen = [false; 64];
+
+        for l in Lines::new(r) {
+            let (n, line) = l?;
+            let tokens = Self::tokens(&line);
+            let fail = |column: usize, expected: &'static str| UsageError::Format {
+                line: n,
+                column,
+                expected,
+            };
+
+            match tokens.first() {
+                Some((_, t)) if Self::parse_codon(t).is_some() => (),
+                _ => continue,
+            }
+
+            let mut t = tokens.iter();
+            while let Some((col, tok)) = t.next() {
+                let i = Self::parse_codon(tok).ok_or_else(|| fail(*col, "a codon"))?;
+                if seen[i] {
+                    return Err(fail(*col, "each codon once"));
+                }
+                seen[i] = true;
+
+                let mut count = None;
+                for (c, y) in t.by_ref() {
+                    match *y {
+                        "(" => {
+                            count = Some(c);
+                            break;
+                        }
+                        _ if Self::parse_codon(y).is_some() => {
+                            return Err(fail(*c, "a count in parentheses"));
+                        }
+                        _ => (),
+                    }
+                }
+                let open = count.ok_or_else(|| fail(line.len() + 1, "a count in parentheses"))?;
+
+                let (c, y) = t.next().ok_or_else(|| fail(open + 1, "a count"))?;
+                x.counts[i] = y.parse::<u64>().map_err(|_| fail(*c, "a count"))?;
+
+                match t.next() {
+                    Some((_, ")")) => (),
+                    Some((c, _)) => return Err(fail(*c, "')'")),
+                    None => return Err(fail(line.len() + 1, "')'")),
+                }
+            }
+        }
+
+        Ok(x)
+    }
+
+    // Writes Kazusa's layout: a block of four lines per first base, the
+    // third base down each block and the second across it.
+    pub fn write<W: Write>(&self, mut w: W) -> io::Result<()> {
+        for fst in 0..4 {
+            if fst > 0 {
+                writeln!(w)?;
+            }
+            for thd in 0..4 {
+                let x: Vec<String> = (0..4)
+                    .map(|snd| {
+                        let i = 16 * fst + 4 * snd + thd;
+                        let c = Self::codon(i);
+                        format!(
+                            "{}{}{} {:4.1}({:6})",
+                            c.0,
+                            c.1,
+                            c.2,
+                            self.per_thousand(&c),
+                            self.counts[i]
+                        )
+                    })
+                    .collect();
+                writeln!(w, "{}", x.join("  "))?;
+            }
+        }
+
+        Ok(())
+    }
+
+    // CAI's relative adaptiveness: c's count over that of the most used
+    // codon for its residue. None for STOP, residues with a single codon and
+    // residues this reference never saw.
+    pub fn weight(&self, c: &(RNA, RNA, RNA)) -> Option<f64> {
+        let i = Self::index(c);
+        let max = self.family_max(&self.aminos[i])?;
+        match self.counts[i] {
+            0 => Some(CAI_PSEUDOCOUNT / max as f64),
+            n => Some(n as f64 / max as f64),
+        }
+    }
+
+    // NOTE: Sharp and Li's Codon Adaptation Index of gene against this
+    // usage as the reference: the geometric mean of the weights of gene's
+    // codons, skipping those with no weight.
+    pub fn cai(&self, gene: &Strand<RNACell>) -> Option<f64> {
+        let mut sum = 0.0;
+        let mut n = 0;
+        for i in Self::indices(gene) {
+            if let Some(w) = self.weight(&Self::codon(i)) {
+                sum += w.ln();
+                n += 1;
+            }
+        }
+
+        match n {
+            0 => None,
+            _ => Some((sum / n as f64).exp()),
+        }
+    }
+
+    // NOTE: Ikemura's frequency of optimal codons. A codon is optimal when
+    // no synonym is used more in this reference; ties are all optimal.
+    // Codons without a weight are skipped, as in cai.
+    pub fn fop(&self, gene: &Strand<RNACell>) -> Option<f64> {
+        let mut optimal = 0;
+        let mut n = 0;
+        for i in Self::indices(gene) {
+            if self.weight(&Self::codon(i)).is_some() {
+                n += 1;
+                if Some(self.counts[i]) == self.family_max(&self.aminos[i]) {
+                    optimal += 1;
+                }
+            }
+        }
+
+        match n {
+            0 => None,
+            _ => Some(optimal as f64 / n as f64),
+        }
+    }
+
+    // NOTE: Wright's (1990) effective number of codons, of this usage
+    // itself; count a single gene to get a gene's. Residues are grouped by
+    // how many codons they have, and each group adds its size over its
+    // mean homozygosity F. A residue seen less than twice has no F. A group
+    // of three with no F (Ile, when absent) takes the mean of the groups
+    // of two and four, as Wright does; any other such group gives None. The
+    // result is capped at the number of sense codons.
+    pub fn enc(&self) -> Option<f64> {
+        let mut groups: Vec<(usize, usize, Vec<f64>)> = Vec::new();
+        for a in self.residues() {
+            let family = self.family(&a);
+            let n = family.iter().map(|&i| self.counts[i]).sum::<u64>() as f64;
+            let f = match n > 1.0 {
+                true => {
+                    let p: f64 = family
+                        .iter()
+                        .map(|&i| (self.counts[i] as f64 / n).powi(2))
+                        .sum();
+                    Some((n * p - 1.0) / (n - 1.0))
+                }
+                _ => None,
+            };
+
+            match groups.iter_mut().find(|x| x.0 == family.len()) {
+                Some(x) => {
+                    x.1 += 1;
+                    x.2.extend(f);
+                }
+                None => groups.push((family.len(), 1, f.into_iter().collect())),
+            }
+        }
+
+        let mean = |k: usize| {
+            groups
+                .iter()
+                .find(|x| x.0 == k && !x.2.is_empty())
+                .map(|x| x.2.iter().sum::<f64>() / x.2.len() as f64)
+        };
+
+        let mut nc = 0.0;
+        for (k, size, _) in groups.iter() {
+            let f = match *k {
+                1 => 1.0,
+                3 => mean(3).or_else(|| Some((mean(2)? + mean(4)?) / 2.0))?,
+                _ => mean(*k)?,
+            };
+            nc += *size as f64 / f;
+        }
+
+        let sense = self.aminos.iter().filter(|&a| a != &Amino::STOP).count();
+        Some(nc.min(sense as f64))
+    }
+
+    // Every residue the code reads some codon as, STOP aside.
+    fn residues(&self) -> Vec<Amino> {
+        let mut x: Vec<Amino> = Vec::new();
+        for a in self.aminos.iter() {
+            if a != &Amino::STOP && !x.contains(a) {
+                x.push(a.clone());
+            }
+        }
+
+        x
+    }
+
+    fn family(&self, a: &Amino) -> Vec<usize> {
+        (0..64).filter(|&i| &self.aminos[i] == a).collect()
+    }
+
+    fn family_total(&self, a: &Amino) -> u64 {
+        self.family(a).iter().map(|&i| self.counts[i]).sum()
+    }
+
+    fn family_max(&self, a: &Amino) -> Option<u64> {
+        let family = self.family(a);
+        match a == &Amino::STOP || family.len() < 2 {
+            true => None,
+            _ => match family.iter().map(|&i| self.counts[i]).max() {
+                Some(0) | None => None,
+                x => x,
+            },
+        }
+    }
+
+    fn indices(gene: &Strand<RNACell>) -> Vec<usize> {
+        let mut x = Vec::new();
+        let mut i = 0;
+        while i + 3 <= gene.len() {
+            let base = |j: usize| {
+                let c = gene.get(j).unwrap();
+                let y = c.read();
+                BASES.iter().position(|b| Some(b) == y.as_ref()).unwrap()
+            };
+            x.push(16 * base(i) + 4 * base(i + 1) + base(i + 2));
+            i += 3;
+        }
+
+        x
+    }
+
+    fn index(c: &(RNA, RNA, RNA)) -> usize {
+        let base = |x: &RNA| BASES.iter().position(|y| y == x).unwrap();
+        16 * base(&c.0) + 4 * base(&c.1) + base(&c.2)
+    }
+
+    fn codon(i: usize) -> (RNA, RNA, RNA) {
+        (
+            BASES[i / 16].clone(),
+            BASES[i / 4 % 4].clone(),
+            BASES[i % 4].clone(),
+        )
+    }
+
+    fn parse_codon(s: &str) -> Option<usize> {
+        let b = s.as_bytes();
+        match b.len() == 3 {
+            true => {
+                let mut i = 0;
+                for x in b.iter() {
+                    let y = match x.to_ascii_uppercase() {
+                        b'U' | b'T' => 0,
+                        b'C' => 1,
+                        b'A' => 2,
+                        b'G' => 3,
+                        _ => return None,
+                    };
+                    i = 4 * i + y;
+                }
+
+                Some(i)
+            }
+            _ => None,
+        }
+    }
+
+    // Whitespace separated tokens with their 1-based columns. Parentheses
+    // are tokens of their own, as Kazusa runs them into the numbers.
+    fn tokens(line: &str) -> Vec<(usize, &str)> {
+        let mut x = Vec::new();
+        let mut from = None;
+        for (i, c) in line.char_indices() {
+            if c.is_whitespace() || c == '(' || c == ')' {
+                if let Some(j) = from.take() {
+                    x.push((j + 1, &line[j..i]));
+                }
+                if c == '(' || c == ')' {
+                    x.push((i + 1, &line[i..i + 1]));
+                }
+            } else if from.is_none() {
+                from = Some(i);
+            }
+        }
+        if let Some(j) = from {
+            x.push((j + 1, &line[j..]));
+        }
+
+        x
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use amino::AminoCat;
+    use category::Cat;
+    use codon::GeneticCode;
+    use rna::RNACat;
+
+    fn genes(ribo: &Ribosome, x: &[&str]) -> Vec<Strand<RNACell>> {
+        x.iter()
+            .map(|s| ribo.rna_c.polymer_from_string(s.replace(" ", "")).unwrap())
+            .collect()
+    }
+
+    fn close(x: f64, y: f64) -> bool {
+        (x - y).abs() < 1e-4
+    }
+
+    #[test]
+    fn usage_tables() {
+        let ribo = Ribosome::new(AminoCat::new(), RNACat::new());
+        let g = genes(&ribo, &["aug gcu gcc uaa", "aug gcu uaa gc"]);
+        let usage = CodonUsage::from_genes(&ribo, &g);
+
+        let gcu = (RNA::G, RNA::C, RNA::U);
+        assert_eq!(usage.total(), 7);
+        assert_eq!(usage.count(&gcu), 2);
+        assert_eq!(usage.amino(&gcu), &Amino::Ala);
+        assert_eq!(usage.synonyms(&Amino::Ala).len(), 4);
+        assert!(close(usage.frequency(&gcu).unwrap(), 2.0 / 3.0));
+        assert!(close(usage.rscu(&gcu).unwrap(), 8.0 / 3.0));
+        assert!(close(usage.per_thousand(&gcu), 2000.0 / 7.0));
+        assert_eq!(usage.rscu(&(RNA::G, RNA::C, RNA::A)), Some(0.0));
+        assert_eq!(usage.frequency(&(RNA::G, RNA::G, RNA::U)), None);
+
+        let table = usage.table();
+        assert_eq!(table.len(), 64);
+        assert_eq!(table[0].codon, (RNA::U, RNA::U, RNA::U));
+        assert_eq!(table[CodonUsage::index(&gcu)].count, 2);
+
+        // Synonyms follow the Ribosome's code.
+        let code = GeneticCode::new(2).unwrap();
+        let mt = Ribosome::with_code(AminoCat::new(), RNACat::new(), code, false);
+        let usage = CodonUsage::new(&mt);
+        assert_eq!(usage.synonyms(&Amino::STOP).len(), 4);
+        assert_eq!(usage.amino(&(RNA::U, RNA::G, RNA::A)), &Amino::Trp);
+    }
+
+    #[test]
+    fn kazusa_round_trip() {
+        let ribo = Ribosome::new(AminoCat::new(), RNACat::new());
+        let g = genes(&ribo, &["aug gcu gcc ggg uuu uga"]);
+        let usage = CodonUsage::from_genes(&ribo, &g);
+
+        let mut out = Vec::new();
+        usage.write(&mut out).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        assert!(text.starts_with(
+            "UUU 166.7(     1)  UCU  0.0(     0)  UAU  0.0(     0)  UGU  0.0(     0)\n"
+        ));
+        assert_eq!(text.lines().count(), 19);
+
+        let back = CodonUsage::read(&ribo, text.as_bytes()).unwrap();
+        assert_eq!(back.table(), usage.table());
+    }
+
+    #[test]
+    fn read_layouts() {
+        let ribo = Ribosome::new(AminoCat::new(), RNACat::new());
+        let text = "fields: [triplet] [frequency: per thousand] ([number])\n\
+                    UUU 17.6(714298)  UCU 15.2(618711)\n\
+                    \n\
+                    TTC F 0.54 20.3 ( 824692)\n";
+        let usage = CodonUsage::read(&ribo, text.as_bytes()).unwrap();
+        assert_eq!(usage.count(&(RNA::U, RNA::U, RNA::U)), 714298);
+        assert_eq!(usage.count(&(RNA::U, RNA::U, RNA::C)), 824692);
+        assert_eq!(usage.total(), 714298 + 618711 + 824692);
+
+        match CodonUsage::read(&ribo, "UUU 17.6(71x)\n".as_bytes()) {
+            Err(UsageError::Format { line, column, .. }) => assert_eq!((line, column), (1, 10)),
+            x => panic!("Expected a format error, got {:?}", x),
+        }
+        match CodonUsage::read(&ribo, "UUU 1(1)\nUUU 1(1)\n".as_bytes()) {
+            Err(UsageError::Format { line, column, .. }) => assert_eq!((line, column), (2, 1)),
+            x => panic!("Expected a format error, got {:?}", x),
+        }
+        match CodonUsage::read(&ribo, "UUU 17.6  UCU 1(1)\n".as_bytes()) {
+            Err(UsageError::Format { column, .. }) => assert_eq!(column, 11),
+            x => panic!("Expected a format error, got {:?}", x),
+        }
+    }
+
+    #[test]
+    fn cai_and_fop() {
+        let ribo = Ribosome::new(AminoCat::new(), RNACat::new());
+        let reference = CodonUsage::from_genes(
+            &ribo,
+            &genes(
+                &ribo,
+                &[
+                    "aug gcu gcu gcu gcu gcu gcu gcu gcu gcc gcc uaa",
+                    "aug ggu ggu ggu ggu ggc ggc ggc ggc uaa",
+                ],
+            ),
+        );
+
+        let gcu = (RNA::G, RNA::C, RNA::U);
+        assert_eq!(reference.weight(&gcu), Some(1.0));
+        assert_eq!(reference.weight(&(RNA::G, RNA::C, RNA::A)), Some(0.0625));
+        assert_eq!(reference.weight(&(RNA::A, RNA::U, RNA::G)), None);
+        assert_eq!(reference.weight(&(RNA::C, RNA::U, RNA::G)), None);
+
+        let gene = genes(&ribo, &["aug gcu gcc ggu cug uaa"]).remove(0);
+        assert!(close(
+            reference.cai(&gene).unwrap(),
+            0.25f64.powf(1.0 / 3.0)
+        ));
+        assert!(close(reference.fop(&gene).unwrap(), 2.0 / 3.0));
+
+        let none = genes(&ribo, &["aug uaa"]).remove(0);
+        assert_eq!(reference.cai(&none), None);
+        assert_eq!(reference.fop(&none), None);
+    }
+
+    #[test]
+    fn effective_number_of_codons() {
+        let ribo = Ribosome::new(AminoCat::new(), RNACat::new());
+        let code = GeneticCode::standard();
+
+        // One codon per residue, each used twice: every F is 1.
+        let biased = |skip: &Amino| -> String {
+            Amino::STANDARD
+                .iter()
+                .filter(|&a| a != skip)
+                .map(|a| {
+                    let (x, y, z) = code.codons(a)[0].clone();
+                    format!("{}{}{}", x, y, z).repeat(2)
+                })
+                .collect()
+        };
+        let usage = CodonUsage::from_genes(&ribo, &genes(&ribo, &[&biased(&Amino::STOP)]));
+        assert!(close(usage.enc().unwrap(), 20.0));
+
+        // Without Ile, the group of three falls back on two and four.
+        let no_ile = biased(&Amino::Ile);
+        let usage = CodonUsage::from_genes(&ribo, &genes(&ribo, &[&no_ile]));
+        assert!(close(usage.enc().unwrap(), 20.0));
+
+        // Every sense codon equally: capped at 61.
+        let even: String = (0..64)
+            .map(CodonUsage::codon)
+            .filter(|c| code.amino(c) != Amino::STOP)
+            .map(|(x, y, z)| format!("{}{}{}", x, y, z).repeat(50))
+            .collect();
+        let usage = CodonUsage::from_genes(&ribo, &genes(&ribo, &[&even]));
+        assert!(close(usage.enc().unwrap(), 61.0));
+
+        let short = CodonUsage::from_genes(&ribo, &genes(&ribo, &["aug gcu gcu"]));
+        assert_eq!(short.enc(), None);
+    }
+}
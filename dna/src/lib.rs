@@ -1,160 +1,234 @@
 #[macro_use]
 extern crate enum_display_derive;
 
-use category::{Cat, ICat, NCat};
-use monomer::{IMono, Mono, NucleicAcid};
-use once_mono::{IMonomer};
-use polymer::{Helix, Polymer, Strand};
+use alphabet::Alphabet;
+use category::ICat;
+use monomer::Packed;
+use polymer::{Helix, PackedStrand, Polymer, Strand};
 
+#[cfg(feature = "parallel")]
+use polymer::PAR_CHUNK;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+use std::convert::TryFrom;
 use std::fmt::Display;
 
-// TODO: Support the other DNA alphabets.
-#[derive(Debug, Display, PartialEq, Clone)]
+#[derive(Debug, Display, PartialEq, Clone, Alphabet)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[alphabet(
+    name = "DNA (ACGT)",
+    cat = "DNACat",
+    cell = "DNACell",
+    polymer = "Helix",
+    nucleic
+)]
 pub enum DNA {
+    #[base(letter = 'A', complement = T)]
     A,
+    #[base(letter = 'T', complement = A)]
     T,
+    #[base(letter = 'G', complement = C, gc)]
     G,
+    #[base(letter = 'C', complement = G, gc)]
     C,
 }
 
-impl NucleicAcid for DNA {
-    fn is_g_or_c(&self) -> bool {
+impl Packed for DNA {
+    fn to_bits(&self) -> u8 {
         match self {
-            DNA::G => true,
-            DNA::C => true,
-            _ => false,
+            DNA::T => 0,
+            DNA::C => 1,
+            DNA::A => 2,
+            DNA::G => 3,
         }
     }
-}
 
-impl Mono for DNA {
-    fn from_string(s: String) -> Option<DNA> {
-        match s.to_uppercase().as_ref() {
-            "A" => Some(DNA::A),
-            "T" => Some(DNA::T),
-            "C" => Some(DNA::C),
-            "G" => Some(DNA::G),
-            _ => None,
+    fn from_bits(b: u8) -> Self {
+        match b & 0b11 {
+            0 => DNA::T,
+            1 => DNA::C,
+            2 => DNA::A,
+            _ => DNA::G,
         }
     }
 }
 
-impl IMono for DNA {
-    fn inverse(c: &Self) -> Self {
-        match c {
-            DNA::A => DNA::T,
-            DNA::T => DNA::A,
-            DNA::C => DNA::G,
-            DNA::G => DNA::C,
+impl DNACat {
+    pub fn pairs(&self, u: Helix<DNACell>) -> Vec<(DNACell, DNACell)> {
+        let mut next = Vec::<(DNACell, DNACell)>::new();
+        for x in u {
+            next.push((x.clone(), self.inverse_m(&x)));
         }
+        next
     }
-}
 
-type DNACell = IMonomer<DNA>;
+    pub fn strands(&self, u: Helix<DNACell>) -> (Strand<DNACell>, Strand<DNACell>) {
+        let x = self.inverse_p(&u);
+        (u.strand, x.strand)
+    }
 
-pub struct DNACat {
-    a: DNACell,
-    t: DNACell,
-    c: DNACell,
-    g: DNACell,
-}
+    pub fn monomer_from_bits(&self, b: u8) -> DNACell {
+        self.cell(&DNA::from_bits(b))
+    }
 
-impl Cat<DNACell, Helix<DNACell>> for DNACat {
-    fn new() -> Self {
-        DNACat {
-            a: IMonomer::from_string(String::from("a")).unwrap(),
-            t: IMonomer::from_string(String::from("t")).unwrap(),
-            c: IMonomer::from_string(String::from("c")).unwrap(),
-            g: IMonomer::from_string(String::from("g")).unwrap(),
-        }
+    // NOTE: Unlike PackedStrand::to_helix, shares this Cat's cells.
+    pub fn unpack(&self, p: &PackedStrand<DNACell>) -> Helix<DNACell> {
+        (0..p.len())
+            .map(|i| self.monomer_from_bits(p.bits(i)))
+            .collect()
     }
+}
 
-    fn monomer_from_string(&self, s: String) -> Option<DNACell> {
-        match DNA::from_string(s) {
-            None => None,
-            Some(x) => match x {
-                DNA::A => Some(self.a.clone()),
-                DNA::T => Some(self.t.clone()),
-                DNA::C => Some(self.c.clone()),
-                DNA::G => Some(self.g.clone()),
-            },
+// NOTE: Same result as inverse_p, spread over rayon's thread pool.
+#[cfg(feature = "parallel")]
+impl DNACat {
+    pub fn par_inverse_p(&self, u: &Helix<DNACell>) -> Helix<DNACell> {
+        let contents = u
+            .strand
+            .contents
+            .par_iter()
+            .with_min_len(PAR_CHUNK)
+            .rev()
+            .map(|z| self.inverse_m(z))
+            .collect();
+
+        Helix {
+            strand: Strand { contents },
         }
     }
+}
 
-    fn polymer_from_string(&self, s: String) -> Option<Helix<DNACell>> {
-        let mut x = Helix::<DNACell>::new();
-        let mut ok = true;
-
-        for c in s.chars() {
-            match self.monomer_from_string(c.to_string()) {
-                Some(y) => x.push(y),
-                None => {
-                    ok = false;
-                    break;
-                }
-            }
-        }
+// NOTE: IUPAC nucleotide codes, for sequences carrying ambiguous bases
+// such as the N-runs of reference assemblies. Only S is certain to be
+// a G or C, other ambiguity codes are not counted towards GC content.
+#[derive(Debug, Display, PartialEq, Clone, Alphabet)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[alphabet(
+    name = "IUPAC DNA (ACGTRYSWKMBDHVN)",
+    cat = "IupacDNACat",
+    cell = "IupacDNACell",
+    polymer = "Helix",
+    nucleic
+)]
+pub enum IupacDNA {
+    #[base(letter = 'A', complement = T)]
+    A,
+    #[base(letter = 'C', complement = G, gc)]
+    C,
+    #[base(letter = 'G', complement = C, gc)]
+    G,
+    #[base(letter = 'T', complement = A)]
+    T,
+    #[base(letter = 'R', complement = Y)]
+    R,
+    #[base(letter = 'Y', complement = R)]
+    Y,
+    #[base(letter = 'S', complement = S, gc)]
+    S,
+    #[base(letter = 'W', complement = W)]
+    W,
+    #[base(letter = 'K', complement = M)]
+    K,
+    #[base(letter = 'M', complement = K)]
+    M,
+    #[base(letter = 'B', complement = V)]
+    B,
+    #[base(letter = 'D', complement = H)]
+    D,
+    #[base(letter = 'H', complement = D)]
+    H,
+    #[base(letter = 'V', complement = B)]
+    V,
+    #[base(letter = 'N', complement = N)]
+    N,
+}
 
-        match ok {
-            true => Some(x),
-            _ => None,
+impl IupacDNA {
+    // The strict bases this code may stand for.
+    pub fn bases(&self) -> Vec<DNA> {
+        match self {
+            IupacDNA::A => vec![DNA::A],
+            IupacDNA::C => vec![DNA::C],
+            IupacDNA::G => vec![DNA::G],
+            IupacDNA::T => vec![DNA::T],
+            IupacDNA::R => vec![DNA::A, DNA::G],
+            IupacDNA::Y => vec![DNA::C, DNA::T],
+            IupacDNA::S => vec![DNA::C, DNA::G],
+            IupacDNA::W => vec![DNA::A, DNA::T],
+            IupacDNA::K => vec![DNA::G, DNA::T],
+            IupacDNA::M => vec![DNA::A, DNA::C],
+            IupacDNA::B => vec![DNA::C, DNA::G, DNA::T],
+            IupacDNA::D => vec![DNA::A, DNA::G, DNA::T],
+            IupacDNA::H => vec![DNA::A, DNA::C, DNA::T],
+            IupacDNA::V => vec![DNA::A, DNA::C, DNA::G],
+            IupacDNA::N => vec![DNA::A, DNA::C, DNA::G, DNA::T],
         }
     }
-}
 
-impl ICat<DNACell, Helix<DNACell>> for DNACat {
-    fn inverse_m(&self, t: &DNACell) -> DNACell {
-        match t.read().as_ref().unwrap() {
-            DNA::A => self.t.clone(),
-            DNA::T => self.a.clone(),
-            DNA::C => self.g.clone(),
-            DNA::G => self.c.clone(),
-        }
+    pub fn matches(&self, d: &DNA) -> bool {
+        self.bases().contains(d)
     }
+}
 
-    fn inverse_p(&self, u: &Helix<DNACell>) -> Helix<DNACell> {
-        let mut y = Helix::<DNACell>::new();
-        for z in &u.strand.contents {
-            y.push(self.inverse_m(&z));
+impl From<DNA> for IupacDNA {
+    fn from(d: DNA) -> Self {
+        match d {
+            DNA::A => IupacDNA::A,
+            DNA::T => IupacDNA::T,
+            DNA::C => IupacDNA::C,
+            DNA::G => IupacDNA::G,
         }
-        y.strand.contents =  y.strand.contents.into_iter().rev().collect();
-        y
     }
 }
 
-impl NCat<DNACell, Helix<DNACell>> for DNACat {
-    fn gc_content(u: Helix<DNACell>) -> (u64, u64) {
-        let mut d = 0;
-        let mut n = 0;
-        for x in u.strand.contents {
-            d = d + 1;
-            if DNACell::is_g_or_c(&x) {
-                n = n + 1;
-            }
+// NOTE: Fails with the ambiguous code that could not be narrowed.
+impl TryFrom<IupacDNA> for DNA {
+    type Error = IupacDNA;
+
+    fn try_from(d: IupacDNA) -> Result<Self, Self::Error> {
+        match d {
+            IupacDNA::A => Ok(DNA::A),
+            IupacDNA::T => Ok(DNA::T),
+            IupacDNA::C => Ok(DNA::C),
+            IupacDNA::G => Ok(DNA::G),
+            x => Err(x),
         }
-
-        (n, d)
     }
 }
 
-impl DNACat {
-    pub fn pairs(&self, u: Helix<DNACell>) -> Vec<(DNACell, DNACell)> {
-        let mut next = Vec::<(DNACell, DNACell)>::new();
-        for x in u.strand.contents {
-            next.push((x.clone(), self.inverse_m(&x)));
+impl IupacDNACat {
+    pub fn from_strict(&self, u: &Helix<DNACell>) -> Helix<IupacDNACell> {
+        let mut y = Helix::<IupacDNACell>::new();
+        for z in u {
+            let x = IupacDNA::from(z.read().as_ref().unwrap().clone());
+            y.push(self.cell(&x));
         }
-        next
+        y
     }
 
-    pub fn strands(&self, u: Helix<DNACell>) -> (Strand<DNACell>, Strand<DNACell>) {
-        let x = self.inverse_p(&u);
-        (u.strand, x.strand)
+    // NOTE: None if any base is ambiguous.
+    pub fn to_strict(&self, u: &Helix<IupacDNACell>, c: &DNACat) -> Option<Helix<DNACell>> {
+        let mut y = Helix::<DNACell>::new();
+        for z in u {
+            let x = DNA::try_from(z.read().as_ref().unwrap().clone()).ok()?;
+            y.push(match x {
+                DNA::A => c.a.clone(),
+                DNA::T => c.t.clone(),
+                DNA::C => c.c.clone(),
+                DNA::G => c.g.clone(),
+            });
+        }
+        Some(y)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use category::Cat;
+    use monomer::{Mono, ParseError};
+    use polymer::{Duplex, IPolymer, Profile, Side};
 
     #[test]
     fn dna_from_char() {
@@ -230,7 +304,15 @@ mod tests {
             &DNA::G
         );
 
-        assert_eq!(c.monomer_from_string(String::from("d")), None);
+        assert_eq!(
+            c.monomer_from_string(String::from("d")),
+            Err(ParseError::Monomer {
+                found: String::from("d"),
+                offset: 0,
+                alphabet: "DNA (ACGT)",
+            })
+        );
+        assert_eq!(c.maybe_monomer_from_string(String::from("d")), None);
     }
 
     // TODO Add Inverse:
@@ -263,13 +345,20 @@ mod tests {
         let maybe_none = c.polymer_from_string(bad_str);
 
         match maybe_h {
-            Some(_) => assert!(true),
-            None => panic!("Recieved 'None' on good input"),
+            Ok(_) => {}
+            Err(e) => panic!("Recieved 'Err({})' on good input", e),
         }
 
         match maybe_none {
-            None => assert!(true),
-            Some(x) => panic!("Recieved 'Some({:?})' on bad input", x),
+            Err(e) => assert_eq!(
+                e,
+                ParseError::Monomer {
+                    found: String::from("b"),
+                    offset: 0,
+                    alphabet: "DNA (ACGT)",
+                }
+            ),
+            Ok(x) => panic!("Recieved 'Ok({:?})' on bad input", x),
         }
     }
 
@@ -360,4 +449,305 @@ mod tests {
         assert_eq!(4, maybe_4);
         assert_eq!(5, maybe_5);
     }
+
+    #[test]
+    fn iupac_from_string() {
+        let c = IupacDNACat::new();
+
+        let h = c.polymer_from_string(String::from("acgtNNNNrykm")).unwrap();
+        assert_eq!(h.strand.contents.len(), 12);
+        assert_eq!(h.strand.contents[4], c.n);
+        assert_eq!(
+            c.polymer_from_string(String::from("acgx")).unwrap_err(),
+            ParseError::Monomer {
+                found: String::from("x"),
+                offset: 3,
+                alphabet: IupacDNA::ALPHABET,
+            }
+        );
+    }
+
+    #[test]
+    fn iupac_inverse() {
+        let c = IupacDNACat::new();
+
+        let h1 = c.polymer_from_string(String::from("ARYSWKMBDHVN")).unwrap();
+        let h2 = c.polymer_from_string(String::from("NBDHVKMWSRYT")).unwrap();
+
+        let i1 = c.inverse_p(&h1);
+        let i2 = c.inverse_p(&i1);
+
+        assert_eq!(i1, h2);
+        assert_eq!(i2, h1);
+    }
+
+    #[test]
+    fn iupac_strict_round_trip() {
+        let c = DNACat::new();
+        let ic = IupacDNACat::new();
+
+        let h1 = c.polymer_from_string(String::from("gattaca")).unwrap();
+        let i1 = ic.from_strict(&h1);
+
+        assert_eq!(i1, ic.polymer_from_string(String::from("gattaca")).unwrap());
+        assert_eq!(ic.to_strict(&i1, &c).unwrap(), h1);
+
+        let i2 = ic.polymer_from_string(String::from("gattNca")).unwrap();
+        assert_eq!(ic.to_strict(&i2, &c), None);
+
+        assert_eq!(DNA::try_from(IupacDNA::R), Err(IupacDNA::R));
+        assert!(IupacDNA::R.matches(&DNA::G));
+        assert!(!IupacDNA::R.matches(&DNA::T));
+    }
+
+    #[test]
+    fn packed_round_trip() {
+        let c = DNACat::new();
+
+        let h = c
+            .polymer_from_string(String::from("gattacagattaca"))
+            .unwrap();
+        let p = PackedStrand::from_helix(&h);
+
+        assert_eq!(p.len(), 14);
+        assert_eq!(p.as_bytes().len(), 4);
+        assert_eq!(p.to_helix(), h);
+        assert_eq!(c.unpack(&p), h);
+        assert_eq!(p.get(1), Some(c.a.clone()));
+        assert_eq!(p.get(14), None);
+    }
+
+    #[test]
+    fn packed_concat() {
+        let c = DNACat::new();
+
+        let mut p1 = PackedStrand::from_helix(&c.polymer_from_string(String::from("gat")).unwrap());
+        let mut p2 =
+            PackedStrand::from_helix(&c.polymer_from_string(String::from("taca")).unwrap());
+        let p3 = PackedStrand::from_helix(&c.polymer_from_string(String::from("gattaca")).unwrap());
+
+        p1.concat(&mut p2);
+
+        assert_eq!(p1, p3);
+        assert!(p2.is_empty());
+    }
+
+    #[test]
+    fn packed_inverse_and_gc() {
+        let c = DNACat::new();
+
+        let h1 = c.polymer_from_string(String::from("atcgg")).unwrap();
+        let h2 = c.polymer_from_string(String::from("ccgat")).unwrap();
+        let p1 = PackedStrand::from_helix(&h1);
+
+        assert_eq!(c.unpack(&p1.inverse()), h2);
+        assert_eq!(p1.inverse().inverse(), p1);
+        assert_eq!(p1.gc_content(), h1.gc_content());
+    }
+
+    #[test]
+    fn polymer_read_api() {
+        let c = DNACat::new();
+
+        let h = c.polymer_from_string(String::from("gattaca")).unwrap();
+
+        assert_eq!(h.len(), 7);
+        assert!(!h.is_empty());
+        assert_eq!(h.get(0), Some(c.g.clone()));
+        assert_eq!(h.get(7), None);
+        assert_eq!(h.iter().filter(|x| x == &c.a).count(), 3);
+        assert_eq!(h.iter().next_back(), Some(c.a.clone()));
+
+        assert_eq!(
+            h.subsequence(1..4).unwrap(),
+            c.polymer_from_string(String::from("att")).unwrap()
+        );
+        assert_eq!(h.subsequence(..).unwrap(), h);
+        assert_eq!(h.subsequence(5..=7), None);
+
+        let h2: Helix<DNACell> = h.iter().collect();
+        assert_eq!(h2, h);
+        assert_eq!(h2.into_iter().count(), 7);
+    }
+
+    #[test]
+    fn packed_read_api() {
+        let c = DNACat::new();
+
+        let h = c.polymer_from_string(String::from("gattaca")).unwrap();
+        let p: PackedStrand<DNACell> = h.iter().collect();
+
+        assert_eq!(p.len(), 7);
+        assert_eq!(p.iter().collect::<Helix<DNACell>>(), h);
+        assert_eq!(
+            c.unpack(&p.subsequence(2..6).unwrap()),
+            h.subsequence(2..6).unwrap()
+        );
+        assert_eq!(p.into_iter().next_back(), Some(c.a.clone()));
+    }
+
+    #[test]
+    fn from_bytes() {
+        let c = DNACat::new();
+
+        assert_eq!(DNA::from_byte(b'g'), Ok(DNA::G));
+        assert_eq!(
+            c.polymer_from_bytes(b"GATTACA").unwrap(),
+            c.polymer_from_string(String::from("gattaca")).unwrap()
+        );
+        assert_eq!(
+            Helix::<DNACell>::from_bytes(b"gattaca").unwrap(),
+            c.polymer_from_bytes(b"gattaca").unwrap()
+        );
+        assert_eq!(
+            c.polymer_from_bytes(b"gaNtaca").unwrap_err(),
+            ParseError::Monomer {
+                found: String::from("N"),
+                offset: 2,
+                alphabet: DNA::ALPHABET,
+            }
+        );
+        assert!(IupacDNACat::new().polymer_from_bytes(b"gaNtaca").is_ok());
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn par_matches_sequential() {
+        let c = DNACat::new();
+
+        let h = c.polymer_from_string("gattacaccg".repeat(5000)).unwrap();
+
+        assert_eq!(c.par_inverse_p(&h), c.inverse_p(&h));
+        assert_eq!(h.par_inverse(), h.inverse());
+        assert_eq!(h.par_gc_content(), h.gc_content());
+    }
+
+    #[test]
+    fn duplex_from_helix() {
+        let c = DNACat::new();
+
+        let h = c.polymer_from_string(String::from("gattaca")).unwrap();
+        let d = Duplex::from_helix(&h);
+
+        assert!(d.is_perfect());
+        assert!(d.mismatches().is_empty());
+        assert_eq!(d.overhangs(), (0, 0));
+
+        let pairs: Vec<(Option<DNACell>, Option<DNACell>)> = h
+            .pairs()
+            .into_iter()
+            .map(|(x, y)| (Some(x), Some(y)))
+            .collect();
+        assert_eq!(d.pairs(), pairs);
+
+        let (top, bottom) = d.strands();
+        assert_eq!((top, bottom), c.strands(h));
+    }
+
+    #[test]
+    fn duplex_mismatch_and_overhang() {
+        let c = DNACat::new();
+
+        // NOTE: Bottom pairs with "gatgaca", so top[3] sits opposite a C,
+        // and the extra "aatt" gives the bottom a 5' sticky end.
+        let top = c
+            .polymer_from_string(String::from("gattaca"))
+            .unwrap()
+            .strand;
+        let bottom = c
+            .polymer_from_string(String::from("aatttgtcatc"))
+            .unwrap()
+            .strand;
+        let d = Duplex::new(top, bottom, 0);
+
+        assert!(!d.is_perfect());
+        assert_eq!(d.mismatches(), vec![(3, 7)]);
+        assert_eq!(d.overhangs(), (0, 4));
+        assert_eq!(d.pairs()[3], (Some(c.t.clone()), Some(c.c.clone())));
+        assert_eq!(d.pairs()[8], (None, Some(c.t.clone())));
+
+        let i = d.inverse();
+        assert_eq!(i.mismatches(), vec![(7, 3)]);
+        assert_eq!(i.overhangs(), (4, 0));
+        assert_eq!(i.inverse(), d);
+    }
+
+    #[test]
+    fn duplex_bulge() {
+        let c = DNACat::new();
+
+        // NOTE: The top strand carries an extra "cc" between "gat" and "taca".
+        let top = c
+            .polymer_from_string(String::from("gatcctaca"))
+            .unwrap()
+            .strand;
+        let bottom = c
+            .polymer_from_string(String::from("tgtaatc"))
+            .unwrap()
+            .strand;
+        let d = Duplex::new(top, bottom, 0).with_gap(Side::Bottom, 4, 2);
+
+        assert!(d.mismatches().is_empty());
+        assert_eq!(d.pairs().len(), 9);
+        assert_eq!(d.pairs()[3], (Some(c.c.clone()), None));
+        assert_eq!(d.overhangs(), (0, 0));
+        assert!(!d.is_perfect());
+        assert!(d.inverse().mismatches().is_empty());
+    }
+
+    #[test]
+    fn gc_profiles() {
+        let c = DNACat::new();
+
+        let h = c.polymer_from_string(String::from("ggggccaattta")).unwrap();
+
+        let gc: Vec<(usize, usize, f64)> = h.profile(Profile::GC, 4, 4).collect();
+        assert_eq!(gc, vec![(0, 4, 1.0), (4, 8, 0.5), (8, 12, 0.0)]);
+
+        let gc_skew: Vec<(usize, usize, f64)> = h.profile(Profile::GCSkew, 6, 3).collect();
+        assert_eq!(
+            gc_skew,
+            vec![(0, 6, 1.0 / 3.0), (3, 9, -1.0 / 3.0), (6, 12, 0.0)]
+        );
+
+        let at_skew: Vec<(usize, usize, f64)> = h.profile(Profile::ATSkew, 4, 4).collect();
+        assert_eq!(at_skew, vec![(0, 4, 0.0), (4, 8, 1.0), (8, 12, -0.5)]);
+
+        let cumulative: Vec<f64> = h
+            .profile(Profile::CumulativeGCSkew, 4, 4)
+            .map(|(_, _, x)| x)
+            .collect();
+        assert_eq!(cumulative, vec![1.0, 0.0, 0.0]);
+
+        let packed = PackedStrand::from_helix(&h);
+        assert_eq!(packed.profile(Profile::GC, 4, 4).collect::<Vec<_>>(), gc);
+        assert_eq!(h.profile(Profile::GC, 100, 1).count(), 1);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn helix_serde_round_trip() {
+        use category::{Intern, MonomerSeed, PolymerSeed};
+        use serde::de::DeserializeSeed;
+
+        let c = DNACat::new();
+        let h = c.polymer_from_string(String::from("gattaca")).unwrap();
+
+        let json = serde_json::to_string(&h).unwrap();
+        assert_eq!(json, r#"["G","A","T","T","A","C","A"]"#);
+
+        let mut de = serde_json::Deserializer::from_str(&json);
+        let back: Helix<DNACell> = c.deserialize_polymer(&mut de).unwrap();
+        assert_eq!(back, h);
+
+        let mut de = serde_json::Deserializer::from_str(&json);
+        let strand: Strand<DNACell> = PolymerSeed::new(&c).deserialize(&mut de).unwrap();
+        assert_eq!(strand, h.strand);
+
+        let mut de = serde_json::Deserializer::from_str(r#""C""#);
+        assert_eq!(MonomerSeed(&c).deserialize(&mut de).unwrap(), c.c);
+
+        let mut de = serde_json::Deserializer::from_str(r#"["G","X"]"#);
+        assert!(c.deserialize_polymer::<Helix<DNACell>, _>(&mut de).is_err());
+    }
 }
@@ -0,0 +1,870 @@
+use amino::{AminoCat, AminoCell};
+use category::{Cat, ICat};
+use dna::{DNACat, DNACell};
+pub use format::FormatError;
+use format::{trim, Lines};
+use monomer::{Mono, ParseError};
+use polymer::{Helix, Polymer, Strand};
+use ribosome::Segment;
+use rna::{RNACat, RNACell};
+use std::error::Error;
+use std::fmt;
+use std::io::{self, BufRead, Read, Seek, SeekFrom, Write};
+use std::marker::PhantomData;
+use std::ops::Range;
+
+pub const LINE_WIDTH: usize = 60;
+
+#[derive(Debug)]
+pub enum FastaError {
+    // Reading failed, or a line of a .fai, or of a FASTA being indexed,
+    // does not fit.
+    Read(FormatError),
+    // Sequence on a 1-based line before any '>' header.
+    MissingHeader {
+        line: usize,
+    },
+    // A sequence line that does not parse, the error's offset is the column.
+    Parse {
+        line: usize,
+        error: ParseError,
+    },
+    // A region that does not name a range of an indexed sequence.
+    Region {
+        region: String,
+        expected: &'static str,
+    },
+    // Fetched bases that do not parse, the error's offset is into the sequence.
+    Sequence {
+        name: String,
+        error: ParseError,
+    },
+}
+
+impl fmt::Display for FastaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FastaError::Read(e) => write!(f, "{}", e),
+            FastaError::MissingHeader { line } => {
+                write!(f, "line {}: sequence before the first header", line)
+            }
+            FastaError::Parse { line, error } => write!(f, "line {}: {}", line, error),
+            FastaError::Region { region, expected } => {
+                write!(f, "region {:?}: expected {}", region, expected)
+            }
+            FastaError::Sequence { name, error } => write!(f, "{}: {}", name, error),
+        }
+    }
+}
+
+impl Error for FastaError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            FastaError::Read(e) => Some(e),
+            FastaError::Parse { error, .. } => Some(error),
+            FastaError::Sequence { error, .. } => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<FormatError> for FastaError {
+    fn from(e: FormatError) -> Self {
+        FastaError::Read(e)
+    }
+}
+
+impl From<io::Error> for FastaError {
+    fn from(e: io::Error) -> Self {
+        FastaError::Read(e.into())
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Record<U> {
+    pub id: String,
+    // Whatever follows the id on the header line.
+    pub description: Option<String>,
+    pub seq: U,
+}
+
+impl<U> Record<U> {
+    // b is the header line after its marker, '>' here and '@' in FASTQ.
+    pub fn from_header(b: &[u8], seq: U) -> Self {
+        let s = String::from_utf8_lossy(b);
+        let (id, description) = match s.trim().split_once(char::is_whitespace) {
+            Some((x, y)) => (x.to_string(), Some(y.trim().to_string())),
+            None => (s.trim().to_string(), None),
+        };
+
+        Record {
+            id,
+            description,
+            seq,
+        }
+    }
+}
+
+// NOTE: Yields one record at a time, holding only that record in memory.
+// Lines are parsed as they are read, so lowercase (soft-masked) bases come
+// out as the Cat's usual cells. Blank lines and '\r' are ignored. An error
+// drops the record it occurs in, reading resumes at the next header, and
+// only an io error ends the stream.
+pub struct Reader<'a, R, C, T, U> {
+    lines: Lines<R>,
+    cat: &'a C,
+    buf: Vec<u8>,
+    current: Option<Record<U>>,
+    skipping: bool,
+    parse: fn(&C, &[u8]) -> Result<U, ParseError>,
+    marker: PhantomData<T>,
+}
+
+pub type DNAReader<'a, R> = Reader<'a, R, DNACat, DNACell, Helix<DNACell>>;
+pub type RNAReader<'a, R> = Reader<'a, R, RNACat, RNACell, Strand<RNACell>>;
+pub type ProteinReader<'a, R> = Reader<'a, R, AminoCat, AminoCell, Strand<AminoCell>>;
+
+impl<'a, R, C, T, U> Reader<'a, R, C, T, U>
+where
+    R: BufRead,
+    C: Cat<T, U>,
+    T: Mono,
+    U: Polymer<T>,
+{
+    pub fn new(inner: R, cat: &'a C) -> Self {
+        Reader {
+            lines: Lines::new(inner),
+            cat,
+            buf: Vec::new(),
+            current: None,
+            skipping: false,
+            parse: |c, b| c.polymer_from_bytes(b),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<'a, R: BufRead> ProteinReader<'a, R> {
+    // NOTE: Protein FASTA is one-letter codes, where AminoCat's own parse
+    // takes three-letter ones, so this reads through polymer_from_one_letter.
+    pub fn protein(inner: R, cat: &'a AminoCat) -> Self {
+        Reader {
+            parse: |c, b| c.polymer_from_one_letter(b),
+            ..Reader::new(inner, cat)
+        }
+    }
+}
+
+impl<'a, R, C, T, U> Iterator for Reader<'a, R, C, T, U>
+where
+    R: BufRead,
+    C: Cat<T, U>,
+    T: Mono,
+    U: Polymer<T>,
+{
+    type Item = Result<Record<U>, FastaError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.lines.read_bytes(&mut self.buf) {
+                Ok(0) => return self.current.take().map(Ok),
+                Ok(_) => {}
+                Err(e) => return Some(Err(e.into())),
+            }
+
+            let l = trim(&self.buf);
+            if l.is_empty() {
+                continue;
+            }
+
+            if l[0] == b'>' {
+                self.skipping = false;
+                let next = Record::from_header(&l[1..], U::new());
+                match self.current.replace(next) {
+                    Some(x) => return Some(Ok(x)),
+                    None => continue,
+                }
+            }
+
+            if self.skipping {
+                continue;
+            }
+
+            let line = self.lines.line();
+            let x = match &mut self.current {
+                Some(x) => x,
+                None => {
+                    self.skipping = true;
+                    return Some(Err(FastaError::MissingHeader { line }));
+                }
+            };
+
+            match (self.parse)(self.cat, l) {
+                Ok(mut y) => x.seq.concat(&mut y),
+                Err(error) => {
+                    self.current = None;
+                    self.skipping = true;
+                    return Some(Err(FastaError::Parse { line, error }));
+                }
+            }
+        }
+    }
+}
+
+// NOTE: A line of a samtools .fai. offset is the byte the sequence starts
+// at, and every line but the last holds line_bases bases in line_bytes
+// bytes, newline included.
+#[derive(Debug, PartialEq, Clone)]
+pub struct FaiEntry {
+    pub name: String,
+    pub len: usize,
+    pub offset: u64,
+    pub line_bases: usize,
+    pub line_bytes: usize,
+}
+
+impl FaiEntry {
+    // Where base i of the sequence sits in the file.
+    fn position(&self, i: usize) -> u64 {
+        self.offset + ((i / self.line_bases) * self.line_bytes + i % self.line_bases) as u64
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct Index {
+    entries: Vec<FaiEntry>,
+}
+
+impl Index {
+    pub fn new(entries: Vec<FaiEntry>) -> Self {
+        Index { entries }
+    }
+
+    pub fn entries(&self) -> &[FaiEntry] {
+        &self.entries
+    }
+
+    pub fn get(&self, name: &str) -> Option<&FaiEntry> {
+        self.entries.iter().find(|x| x.name == name)
+    }
+
+    // NOTE: As samtools faidx, every line of a record but its last must be
+    // the same length, and blank lines may only end a record.
+    pub fn build<R: BufRead>(r: R) -> Result<Self, FastaError> {
+        let mut entries = Vec::<FaiEntry>::new();
+        let mut lines = Lines::new(r);
+        let mut buf = Vec::new();
+        let mut offset = 0u64;
+        // Set once a record has had a line shorter than its first.
+        let mut done = false;
+
+        loop {
+            let n = lines.read_bytes(&mut buf)?;
+            if n == 0 {
+                return Ok(Index { entries });
+            }
+            let line = lines.line();
+            let err = |expected| FormatError::Format {
+                line,
+                column: 1,
+                expected,
+            };
+            offset += n as u64;
+
+            if buf.first() == Some(&b'>') {
+                let name = String::from_utf8_lossy(trim(&buf[1..]))
+                    .split_whitespace()
+                    .next()
+                    .unwrap_or("")
+                    .to_string();
+                entries.push(FaiEntry {
+                    name,
+                    len: 0,
+                    offset,
+                    line_bases: 0,
+                    line_bytes: 0,
+                });
+                done = false;
+                continue;
+            }
+
+            let bases = trim(&buf).len();
+            let x = match entries.last_mut() {
+                Some(x) => x,
+                None if bases == 0 => continue,
+                None => return Err(FastaError::MissingHeader { line }),
+            };
+
+            if bases == 0 {
+                done = true;
+                continue;
+            }
+            if done {
+                return Err(err("lines of equal length").into());
+            }
+
+            // NOTE: The last line of a file may have no terminator to
+            // compare.
+            let ended = n > buf.len();
+            if x.line_bases == 0 {
+                x.line_bases = bases;
+                x.line_bytes = n;
+            } else if bases > x.line_bases || (ended && n - bases != x.line_bytes - x.line_bases) {
+                return Err(err("lines of equal length").into());
+            }
+
+            done = bases < x.line_bases;
+            x.len += bases;
+        }
+    }
+
+    pub fn read<R: BufRead>(r: R) -> Result<Self, FastaError> {
+        let mut entries = Vec::new();
+
+        for x in Lines::new(r) {
+            let (line, l) = x?;
+            if l.trim().is_empty() {
+                continue;
+            }
+
+            let columns: Vec<&str> = l.split('\t').collect();
+            let err = |column, expected| {
+                FastaError::from(FormatError::Format {
+                    line,
+                    column,
+                    expected,
+                })
+            };
+            if columns.len() < 5 {
+                return Err(err(columns.len() + 1, "five tab separated columns"));
+            }
+
+            let x = FaiEntry {
+                name: columns[0].to_string(),
+                len: columns[1].parse().map_err(|_| err(2, "a length"))?,
+                offset: columns[2].parse().map_err(|_| err(3, "an offset"))?,
+                line_bases: columns[3].parse().map_err(|_| err(4, "bases per line"))?,
+                line_bytes: columns[4].parse().map_err(|_| err(5, "bytes per line"))?,
+            };
+            // NOTE: Only an empty sequence, which is never seeked into, may
+            // have no bases per line.
+            if x.line_bases == 0 && x.len > 0 {
+                return Err(err(4, "bases per line above 0"));
+            }
+            if x.line_bytes < x.line_bases {
+                return Err(err(5, "bytes per line of at least bases per line"));
+            }
+            entries.push(x);
+        }
+
+        Ok(Index { entries })
+    }
+
+    pub fn write<W: Write>(&self, w: &mut W) -> io::Result<()> {
+        for x in self.entries.iter() {
+            writeln!(
+                w,
+                "{}\t{}\t{}\t{}\t{}",
+                x.name, x.len, x.offset, x.line_bases, x.line_bytes
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+// NOTE: Parsed from samtools style "chr1", "chr1:1000" or "chr1:1000-2000",
+// which are 1-based and inclusive, and held 0-based and half open. An end
+// of None runs to the end of the sequence.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Region {
+    pub name: String,
+    pub start: usize,
+    pub end: Option<usize>,
+}
+
+impl Region {
+    pub fn parse(s: &str) -> Option<Self> {
+        let (name, range) = match s.rsplit_once(':') {
+            Some(x) => x,
+            None => {
+                return Some(Region {
+                    name: s.to_string(),
+                    start: 0,
+                    end: None,
+                })
+            }
+        };
+
+        let number = |x: &str| x.replace(',', "").parse::<usize>().ok();
+        let (start, end) = match range.split_once('-') {
+            Some((x, y)) => (number(x)?, Some(number(y)?)),
+            None => (number(range)?, None),
+        };
+        if name.is_empty() || start == 0 || end.is_some_and(|y| y < start) {
+            return None;
+        }
+
+        Some(Region {
+            name: name.to_string(),
+            start: start - 1,
+            end,
+        })
+    }
+}
+
+// NOTE: Fetches seek straight to the bytes of the range asked for, so only
+// those are read, however large the file.
+pub struct IndexedReader<R> {
+    inner: R,
+    index: Index,
+}
+
+impl<R: Read + Seek> IndexedReader<R> {
+    pub fn new(inner: R, index: Index) -> Self {
+        IndexedReader { inner, index }
+    }
+
+    pub fn index(&self) -> &Index {
+        &self.index
+    }
+
+    // Bases [start, end) of name, 0-based.
+    pub fn fetch(
+        &mut self,
+        name: &str,
+        start: usize,
+        end: usize,
+        cat: &DNACat,
+    ) -> Result<Helix<DNACell>, FastaError> {
+        let err = |expected| FastaError::Region {
+            region: format!("{}:{}-{}", name, start + 1, end),
+            expected,
+        };
+        let x = self
+            .index
+            .get(name)
+            .ok_or_else(|| err("a sequence in the index"))?;
+        if start > end || end > x.len {
+            return Err(err("a range inside the sequence"));
+        }
+        if start == end {
+            return Ok(Helix::new());
+        }
+
+        let from = x.position(start);
+        let mut bytes = vec![0u8; (x.position(end - 1) + 1 - from) as usize];
+        self.inner.seek(SeekFrom::Start(from))?;
+        self.inner.read_exact(&mut bytes)?;
+        bytes.retain(|b| *b != b'\n' && *b != b'\r');
+
+        cat.polymer_from_bytes(&bytes)
+            .map_err(|error| FastaError::Sequence {
+                name: name.to_string(),
+                error: error.offset_by(start),
+            })
+    }
+
+    // As fetch, reverse complemented where reverse is set.
+    pub fn fetch_stranded(
+        &mut self,
+        name: &str,
+        start: usize,
+        end: usize,
+        reverse: bool,
+        cat: &DNACat,
+    ) -> Result<Helix<DNACell>, FastaError> {
+        let x = self.fetch(name, start, end, cat)?;
+        match reverse {
+            true => Ok(cat.inverse_p(&x)),
+            _ => Ok(x),
+        }
+    }
+
+    // NOTE: A region that is a name in the index as it stands is read as
+    // the whole of that sequence, so names holding ':' still work.
+    pub fn query(&mut self, region: &str, cat: &DNACat) -> Result<Helix<DNACell>, FastaError> {
+        let r = match self.index.get(region) {
+            Some(x) => Region {
+                name: region.to_string(),
+                start: 0,
+                end: Some(x.len),
+            },
+            None => Region::parse(region).ok_or_else(|| FastaError::Region {
+                region: region.to_string(),
+                expected: "name, name:start or name:start-end",
+            })?,
+        };
+
+        let end = match (r.end, self.index.get(&r.name)) {
+            (Some(y), _) => y,
+            (None, Some(x)) => x.len,
+            (None, None) => r.start,
+        };
+        self.fetch(&r.name, r.start, end, cat)
+    }
+}
+
+pub struct Writer<W> {
+    inner: W,
+    width: usize,
+}
+
+impl<W: Write> Writer<W> {
+    // Wraps sequence lines at LINE_WIDTH monomers.
+    pub fn new(inner: W) -> Self {
+        Self::with_width(inner, LINE_WIDTH)
+    }
+
+    // A width of 0 writes each sequence on a single line.
+    pub fn with_width(inner: W, width: usize) -> Self {
+        Writer { inner, width }
+    }
+
+    pub fn write<T, U>(&mut self, r: &Record<U>) -> io::Result<()>
+    where
+        T: Mono + fmt::Display,
+        U: Polymer<T>,
+    {
+        self.write_header(&r.id, &r.description)?;
+        self.write_body(r.seq.iter().map(|x| x.to_string()))
+    }
+
+    // NOTE: One-letter codes with '*' for STOP. START has no code, so a
+    // protein holding one is an InvalidInput error.
+    pub fn write_protein(&mut self, r: &Record<Strand<AminoCell>>) -> io::Result<()> {
+        let seq = r
+            .seq
+            .iter()
+            .map(|x| x.read().as_ref().and_then(|y| y.one_letter()))
+            .collect::<Option<String>>()
+            .ok_or_else(|| {
+                io::Error::new(io::ErrorKind::InvalidInput, "START has no one-letter code")
+            })?;
+
+        self.write_header(&r.id, &r.description)?;
+        self.write_body(seq.chars().map(String::from))
+    }
+
+    // NOTE: Writes the proteins of Ribosome::translate_spans, as
+    // >source_index source=source segment=index nt=start-end, where index
+    // counts every segment, junk too, and nt is 1-based and inclusive.
+    pub fn write_segments(
+        &mut self,
+        source: &str,
+        segments: &[(Segment, Range<usize>)],
+    ) -> io::Result<()> {
+        for (i, (x, nt)) in segments.iter().enumerate() {
+            if let Segment::Protein(seq) = x {
+                let r = Record {
+                    id: format!("{}_{}", source, i),
+                    description: Some(format!(
+                        "source={} segment={} nt={}-{}",
+                        source,
+                        i,
+                        nt.start + 1,
+                        nt.end
+                    )),
+                    seq: seq.subsequence(..).unwrap(),
+                };
+                self.write_protein(&r)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn write_header(&mut self, id: &str, description: &Option<String>) -> io::Result<()> {
+        match description {
+            Some(d) => writeln!(self.inner, ">{} {}", id, d),
+            None => writeln!(self.inner, ">{}", id),
+        }
+    }
+
+    fn write_body<I: Iterator<Item = String>>(&mut self, seq: I) -> io::Result<()> {
+        let mut line = String::new();
+        for (i, x) in seq.enumerate() {
+            line.push_str(&x);
+            if self.width > 0 && (i + 1) % self.width == 0 {
+                writeln!(self.inner, "{}", line)?;
+                line.clear();
+            }
+        }
+
+        if !line.is_empty() {
+            writeln!(self.inner, "{}", line)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use amino::Amino;
+    use ribosome::Ribosome;
+
+    const INPUT: &str = ">chr1 first record\nGATTACA\nggcc\n\n>chr2\r\nAC\r\ngt\r\n\n";
+
+    #[test]
+    fn read_dna() {
+        let c = DNACat::new();
+        let records: Vec<Record<Helix<DNACell>>> = DNAReader::new(INPUT.as_bytes(), &c)
+            .collect::<Result<_, _>>()
+            .unwrap();
+
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].id, "chr1");
+        assert_eq!(records[0].description, Some(String::from("first record")));
+        assert_eq!(
+            records[0].seq,
+            c.polymer_from_string(String::from("gattacaggcc")).unwrap()
+        );
+        assert_eq!(records[1].id, "chr2");
+        assert_eq!(records[1].description, None);
+        assert_eq!(
+            records[1].seq,
+            c.polymer_from_string(String::from("acgt")).unwrap()
+        );
+    }
+
+    #[test]
+    fn read_rna() {
+        let c = RNACat::new();
+        let mut r = RNAReader::new(">x\nAUG\ncuaa\n".as_bytes(), &c);
+
+        let x = r.next().unwrap().unwrap();
+        assert_eq!(
+            x.seq,
+            c.polymer_from_string(String::from("augcuaa")).unwrap()
+        );
+        assert!(r.next().is_none());
+    }
+
+    #[test]
+    fn read_errors() {
+        let c = DNACat::new();
+        let input = "ACGT\n>bad\nACGT\nACXT\nACGT\n>good\nTT\n";
+        let x: Vec<Result<Record<Helix<DNACell>>, FastaError>> =
+            DNAReader::new(input.as_bytes(), &c).collect();
+
+        assert_eq!(x.len(), 3);
+        match &x[0] {
+            Err(FastaError::MissingHeader { line: 1 }) => {}
+            y => panic!("expected a missing header, got {:?}", y),
+        }
+        match &x[1] {
+            Err(FastaError::Parse { line: 4, error }) => {
+                assert_eq!(
+                    error,
+                    &ParseError::monomer(String::from("X"), "DNA (ACGT)").offset_by(2)
+                )
+            }
+            y => panic!("expected a parse error, got {:?}", y),
+        }
+        assert_eq!(x[2].as_ref().unwrap().id, "good");
+    }
+
+    #[test]
+    fn write_widths() {
+        let c = DNACat::new();
+        let r = Record {
+            id: String::from("chr1"),
+            description: Some(String::from("first record")),
+            seq: c.polymer_from_string(String::from("gattacagg")).unwrap(),
+        };
+
+        let mut w = Writer::with_width(Vec::new(), 4);
+        w.write(&r).unwrap();
+        assert_eq!(
+            String::from_utf8(w.into_inner()).unwrap(),
+            ">chr1 first record\nGATT\nACAG\nG\n"
+        );
+
+        let mut w = Writer::with_width(Vec::new(), 0);
+        w.write(&r).unwrap();
+        assert_eq!(
+            String::from_utf8(w.into_inner()).unwrap(),
+            ">chr1 first record\nGATTACAGG\n"
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        let c = DNACat::new();
+        let records: Vec<Record<Helix<DNACell>>> = DNAReader::new(INPUT.as_bytes(), &c)
+            .collect::<Result<_, _>>()
+            .unwrap();
+
+        let mut w = Writer::with_width(Vec::new(), 3);
+        for r in records.iter() {
+            w.write(r).unwrap();
+        }
+        let out = w.into_inner();
+
+        let again: Vec<Record<Helix<DNACell>>> = DNAReader::new(&out[..], &c)
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(again, records);
+    }
+
+    const INDEXED: &str = ">chr1 desc\nACGTA\nCCGGT\nTT\n>chr2\r\nGGGA\r\nTC\r\n>chr:3\nAC\n";
+
+    #[test]
+    fn build_index() {
+        let x = Index::build(INDEXED.as_bytes()).unwrap();
+        let mut out = Vec::new();
+        x.write(&mut out).unwrap();
+        assert_eq!(
+            String::from_utf8(out.clone()).unwrap(),
+            "chr1\t12\t11\t5\t6\nchr2\t6\t33\t4\t6\nchr:3\t2\t50\t2\t3\n"
+        );
+        assert_eq!(Index::read(&out[..]).unwrap(), x);
+
+        match Index::build(">a\nACG\nA\nAC\n".as_bytes()) {
+            Err(FastaError::Read(FormatError::Format { line: 4, .. })) => {}
+            y => panic!("expected an index error, got {:?}", y),
+        }
+        match Index::build(">a\nAC\nACG\n".as_bytes()) {
+            Err(FastaError::Read(FormatError::Format { line: 3, .. })) => {}
+            y => panic!("expected an index error, got {:?}", y),
+        }
+        match Index::read("chr1\t12\tx\t5\t6\n".as_bytes()) {
+            Err(FastaError::Read(FormatError::Format {
+                line: 1, column: 3, ..
+            })) => {}
+            y => panic!("expected an index error, got {:?}", y),
+        }
+        match Index::read("chr1\t12\t6\t0\t1\n".as_bytes()) {
+            Err(FastaError::Read(FormatError::Format {
+                line: 1, column: 4, ..
+            })) => {}
+            y => panic!("expected an index error, got {:?}", y),
+        }
+        match Index::read("chr1\t12\t6\t5\t4\n".as_bytes()) {
+            Err(FastaError::Read(FormatError::Format {
+                line: 1, column: 5, ..
+            })) => {}
+            y => panic!("expected an index error, got {:?}", y),
+        }
+
+        // No newline on the last line, as samtools allows.
+        let x = Index::build(">a\nACGT\nAC".as_bytes()).unwrap();
+        assert_eq!(x.get("a").unwrap().len, 6);
+        assert_eq!(x.get("a").unwrap().line_bytes, 5);
+        let mut r = IndexedReader::new(io::Cursor::new(">a\nACGT\nAC".as_bytes()), x);
+        let c = DNACat::new();
+        assert_eq!(
+            r.fetch("a", 2, 6, &c).unwrap(),
+            c.polymer_from_string(String::from("gtac")).unwrap()
+        );
+
+        // An empty record round trips.
+        let x = Index::build(">a\n>b\nAC\n".as_bytes()).unwrap();
+        let mut out = Vec::new();
+        x.write(&mut out).unwrap();
+        assert_eq!(Index::read(&out[..]).unwrap(), x);
+    }
+
+    #[test]
+    fn indexed_fetch() {
+        let c = DNACat::new();
+        let index = Index::build(INDEXED.as_bytes()).unwrap();
+        let mut r = IndexedReader::new(io::Cursor::new(INDEXED.as_bytes()), index);
+        let seq = |s: &str| c.polymer_from_string(String::from(s)).unwrap();
+
+        assert_eq!(r.fetch("chr1", 0, 12, &c).unwrap(), seq("acgtaccggttt"));
+        assert_eq!(r.fetch("chr1", 3, 8, &c).unwrap(), seq("taccg"));
+        assert_eq!(r.fetch("chr1", 4, 4, &c).unwrap(), seq(""));
+        assert_eq!(r.query("chr1:4-8", &c).unwrap(), seq("taccg"));
+        assert_eq!(r.query("chr1:1,1", &c).unwrap(), seq("tt"));
+        assert_eq!(r.query("chr2", &c).unwrap(), seq("gggatc"));
+        assert_eq!(r.query("chr2:3-5", &c).unwrap(), seq("gat"));
+        assert_eq!(r.query("chr:3", &c).unwrap(), seq("ac"));
+        assert_eq!(
+            r.fetch_stranded("chr1", 3, 8, true, &c).unwrap(),
+            seq("cggta")
+        );
+
+        for region in ["chr1:0-3", "chr9:1-2", "chr1:5-13", "chr1:5-4"].iter() {
+            match r.query(region, &c) {
+                Err(FastaError::Region { .. }) => {}
+                y => panic!("expected a region error, got {:?}", y),
+            }
+        }
+
+        let bad = ">x\nACXT\n";
+        let index = Index::build(bad.as_bytes()).unwrap();
+        let mut r = IndexedReader::new(io::Cursor::new(bad.as_bytes()), index);
+        match r.fetch("x", 1, 4, &c) {
+            Err(FastaError::Sequence { error, .. }) => {
+                assert_eq!(
+                    error,
+                    ParseError::monomer(String::from("X"), "DNA (ACGT)").offset_by(2)
+                )
+            }
+            y => panic!("expected a sequence error, got {:?}", y),
+        }
+    }
+
+    #[test]
+    fn protein_segments() {
+        let ribo = Ribosome::new(AminoCat::new(), RNACat::new());
+        let strand = ribo
+            .rna_c
+            .polymer_from_string(String::from("ccaugcagugguaaaugaaauagg"))
+            .unwrap();
+        let spans = ribo.translate_spans(strand).unwrap();
+
+        let mut w = Writer::with_width(Vec::new(), 1);
+        w.write_segments("tx1", &spans).unwrap();
+        let out = String::from_utf8(w.into_inner()).unwrap();
+        assert_eq!(
+            out,
+            ">tx1_1 source=tx1 segment=1 nt=3-14\nQ\nW\n>tx1_2 source=tx1 segment=2 nt=15-23\nK\n"
+        );
+
+        let again: Vec<Record<Strand<AminoCell>>> =
+            ProteinReader::protein(out.as_bytes(), &ribo.amino_c)
+                .collect::<Result<_, _>>()
+                .unwrap();
+        assert_eq!(again.len(), 2);
+        assert_eq!(again[0].id, "tx1_1");
+        match &spans[1].0 {
+            Segment::Protein(x) => assert_eq!(&again[0].seq, x),
+            _ => panic!("expected a protein"),
+        }
+    }
+
+    #[test]
+    fn read_protein() {
+        let c = AminoCat::new();
+        let input = ">p1\nMa\nk*\n>p2\nM1\n";
+        let x: Vec<Result<Record<Strand<AminoCell>>, FastaError>> =
+            ProteinReader::protein(input.as_bytes(), &c).collect();
+
+        let mut p = c.polymer_from_string(String::from("metalalys")).unwrap();
+        p.push(c.morphisms.stop.clone());
+        assert_eq!(x[0].as_ref().unwrap().seq, p);
+        match &x[1] {
+            Err(FastaError::Parse { line: 5, error }) => assert_eq!(
+                error,
+                &ParseError::monomer(String::from("1"), Amino::ONE_LETTER).offset_by(1)
+            ),
+            y => panic!("expected a parse error, got {:?}", y),
+        }
+
+        let mut w = Writer::new(Vec::new());
+        let start = Record {
+            id: String::from("p"),
+            description: None,
+            seq: vec![c.morphisms.start.clone()].into_iter().collect(),
+        };
+        assert!(w.write_protein(&start).is_err());
+    }
+}
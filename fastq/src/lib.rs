@@ -0,0 +1,442 @@
+use category::Cat;
+use dna::{IupacDNACat, IupacDNACell};
+use fasta::Record;
+pub use format::FormatError;
+use format::{trim, Lines};
+use monomer::{Mono, ParseError};
+use polymer::{Polymer, QualityStrand};
+use std::error::Error;
+use std::fmt;
+use std::io::{self, BufRead, Write};
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Encoding {
+    // Sanger and Illumina 1.8+, scores from '!'.
+    Phred33,
+    // Illumina 1.3 to 1.7, scores from '@'.
+    Phred64,
+}
+
+impl Encoding {
+    pub fn offset(self) -> u8 {
+        match self {
+            Encoding::Phred33 => 33,
+            Encoding::Phred64 => 64,
+        }
+    }
+
+    pub fn decode(self, b: u8) -> Option<u8> {
+        match (self.offset()..=b'~').contains(&b) {
+            true => Some(b - self.offset()),
+            _ => None,
+        }
+    }
+
+    // NOTE: Scores past '~' are clamped to the highest one the encoding has.
+    pub fn encode(self, q: u8) -> u8 {
+        q.min(b'~' - self.offset()) + self.offset()
+    }
+}
+
+#[derive(Debug)]
+pub enum FastqError {
+    // Reading failed, or a line does not fit the four line record layout.
+    Read(FormatError),
+    // A sequence line that does not parse, the error's offset is the column.
+    Parse {
+        line: usize,
+        error: ParseError,
+    },
+    // A quality byte outside the encoding, at a 0-based column.
+    Quality {
+        line: usize,
+        offset: usize,
+        found: u8,
+    },
+    // A quality line whose length differs from the sequence line's.
+    Length {
+        line: usize,
+        bases: usize,
+        scores: usize,
+    },
+}
+
+impl fmt::Display for FastqError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FastqError::Read(e) => write!(f, "{}", e),
+            FastqError::Parse { line, error } => write!(f, "line {}: {}", line, error),
+            FastqError::Quality {
+                line,
+                offset,
+                found,
+            } => write!(
+                f,
+                "line {}: unexpected quality {:?} at offset {}",
+                line, *found as char, offset
+            ),
+            FastqError::Length {
+                line,
+                bases,
+                scores,
+            } => write!(
+                f,
+                "line {}: {} quality scores for {} bases",
+                line, scores, bases
+            ),
+        }
+    }
+}
+
+impl Error for FastqError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            FastqError::Read(e) => Some(e),
+            FastqError::Parse { error, .. } => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<FormatError> for FastqError {
+    fn from(e: FormatError) -> Self {
+        FastqError::Read(e)
+    }
+}
+
+impl From<io::Error> for FastqError {
+    fn from(e: io::Error) -> Self {
+        FastqError::Read(e.into())
+    }
+}
+
+fn layout(line: usize, expected: &'static str) -> FastqError {
+    FastqError::Read(FormatError::Format {
+        line,
+        column: 1,
+        expected,
+    })
+}
+
+// NOTE: Reads four line records, blank lines between records are skipped.
+// A record whose lines are all there but whose bases or scores are bad
+// (Parse, Quality, Length) is an error and reading goes on with the next
+// one, as with FASTA. SEQ is read as IUPAC DNA, so reads with N are kept.
+// A broken layout (Read) has no safe place to pick up again ('@' can open
+// a quality line), so it ends the stream, as an io error does.
+pub struct Reader<'a, R> {
+    lines: Lines<R>,
+    cat: &'a IupacDNACat,
+    encoding: Encoding,
+}
+
+impl<'a, R: BufRead> Reader<'a, R> {
+    pub fn new(inner: R, cat: &'a IupacDNACat, encoding: Encoding) -> Self {
+        Reader {
+            lines: Lines::new(inner),
+            cat,
+            encoding,
+        }
+    }
+
+    fn read_line(&mut self, b: &mut Vec<u8>) -> io::Result<bool> {
+        match self.lines.read_bytes(b)? {
+            0 => Ok(false),
+            _ => {
+                let n = trim(b).len();
+                b.truncate(n);
+                Ok(true)
+            }
+        }
+    }
+
+    fn expect_line(&mut self, b: &mut Vec<u8>, expected: &'static str) -> Result<(), FastqError> {
+        match self.read_line(b)? {
+            true => Ok(()),
+            _ => Err(layout(self.lines.line() + 1, expected)),
+        }
+    }
+
+    fn record(&mut self) -> Result<Option<Record<QualityStrand<IupacDNACell>>>, FastqError> {
+        let mut header = Vec::new();
+        loop {
+            if !self.read_line(&mut header)? {
+                return Ok(None);
+            }
+            if !header.is_empty() {
+                break;
+            }
+        }
+
+        if header[0] != b'@' {
+            return Err(layout(self.lines.line(), "'@' header"));
+        }
+
+        let mut seq = Vec::new();
+        self.expect_line(&mut seq, "a sequence line")?;
+        let seq_line = self.lines.line();
+        let strand = self.cat.polymer_from_bytes(&seq);
+
+        let mut plus = Vec::new();
+        self.expect_line(&mut plus, "'+' separator")?;
+        if plus.first() != Some(&b'+') {
+            return Err(layout(self.lines.line(), "'+' separator"));
+        }
+
+        let mut qual = Vec::new();
+        self.expect_line(&mut qual, "a quality line")?;
+
+        // NOTE: The whole record is read before a bad sequence is reported,
+        // so the next call starts at the next record.
+        let strand = match strand {
+            Ok(x) => x.strand,
+            Err(error) => {
+                return Err(FastqError::Parse {
+                    line: seq_line,
+                    error,
+                })
+            }
+        };
+        if qual.len() != strand.len() {
+            return Err(FastqError::Length {
+                line: self.lines.line(),
+                bases: strand.len(),
+                scores: qual.len(),
+            });
+        }
+
+        let mut quality = Vec::with_capacity(qual.len());
+        for (offset, b) in qual.iter().enumerate() {
+            match self.encoding.decode(*b) {
+                Some(q) => quality.push(q),
+                None => {
+                    return Err(FastqError::Quality {
+                        line: self.lines.line(),
+                        offset,
+                        found: *b,
+                    })
+                }
+            }
+        }
+
+        let seq = QualityStrand::from_parts(strand, quality).unwrap();
+        Ok(Some(Record::from_header(&header[1..], seq)))
+    }
+}
+
+impl<'a, R: BufRead> Iterator for Reader<'a, R> {
+    type Item = Result<Record<QualityStrand<IupacDNACell>>, FastqError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.record() {
+            Ok(x) => x.map(Ok),
+            Err(e) => {
+                if let FastqError::Read(_) = e {
+                    self.lines.stop();
+                }
+                Some(Err(e))
+            }
+        }
+    }
+}
+
+pub struct Writer<W> {
+    inner: W,
+    encoding: Encoding,
+}
+
+impl<W: Write> Writer<W> {
+    pub fn new(inner: W, encoding: Encoding) -> Self {
+        Writer { inner, encoding }
+    }
+
+    pub fn write<T>(&mut self, r: &Record<QualityStrand<T>>) -> io::Result<()>
+    where
+        T: Mono + fmt::Display,
+    {
+        match &r.description {
+            Some(d) => writeln!(self.inner, "@{} {}", r.id, d)?,
+            None => writeln!(self.inner, "@{}", r.id)?,
+        }
+
+        let seq: String = r.seq.iter().map(|x| x.to_string()).collect();
+        writeln!(self.inner, "{}\n+", seq)?;
+
+        let mut qual: Vec<u8> = r
+            .seq
+            .quality()
+            .iter()
+            .map(|q| self.encoding.encode(*q))
+            .collect();
+        qual.push(b'\n');
+        self.inner.write_all(&qual)
+    }
+
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use polymer::IPolymer;
+
+    const INPUT: &str = "@read1 lane 1\nGATtaca\n+\nII5#!+I\n\n@read2\r\nAC\r\n+read2\r\n&'\r\n";
+
+    fn read(
+        input: &str,
+        encoding: Encoding,
+    ) -> Vec<Result<Record<QualityStrand<IupacDNACell>>, FastqError>> {
+        let c = IupacDNACat::new();
+        Reader::new(input.as_bytes(), &c, encoding).collect()
+    }
+
+    #[test]
+    fn read_phred33() {
+        let c = IupacDNACat::new();
+        let records: Vec<Record<QualityStrand<IupacDNACell>>> =
+            Reader::new(INPUT.as_bytes(), &c, Encoding::Phred33)
+                .collect::<Result<_, _>>()
+                .unwrap();
+
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].id, "read1");
+        assert_eq!(records[0].description, Some(String::from("lane 1")));
+        assert_eq!(
+            records[0].seq.strand(),
+            &c.polymer_from_string(String::from("gattaca"))
+                .unwrap()
+                .strand
+        );
+        assert_eq!(records[0].seq.quality(), &[40, 40, 20, 2, 0, 10, 40]);
+        assert_eq!(records[1].id, "read2");
+        assert_eq!(records[1].seq.quality(), &[5, 6]);
+    }
+
+    #[test]
+    fn read_phred64() {
+        let x = read("@r\nACGT\n+\nh@JB\n", Encoding::Phred64);
+        assert_eq!(x[0].as_ref().unwrap().seq.quality(), &[40, 0, 10, 2]);
+
+        match &read("@r\nACGT\n+\nh@J!\n", Encoding::Phred64)[0] {
+            Err(FastqError::Quality {
+                line: 4,
+                offset: 3,
+                found: b'!',
+            }) => {}
+            y => panic!("expected a quality error, got {:?}", y),
+        }
+    }
+
+    #[test]
+    fn read_errors() {
+        match &read("@r\nACXT\n+\nIIII\n", Encoding::Phred33)[..] {
+            [Err(FastqError::Parse { line: 2, .. })] => {}
+            y => panic!("expected a parse error, got {:?}", y),
+        }
+        match &read("@r\nACGT\n+\nIII\n", Encoding::Phred33)[..] {
+            [Err(FastqError::Length {
+                line: 4,
+                bases: 4,
+                scores: 3,
+            })] => {}
+            y => panic!("expected a length error, got {:?}", y),
+        }
+        match &read(">r\nACGT\n", Encoding::Phred33)[..] {
+            [Err(FastqError::Read(FormatError::Format { line: 1, .. }))] => {}
+            y => panic!("expected a format error, got {:?}", y),
+        }
+        match &read("@r\nACGT\n+\n", Encoding::Phred33)[..] {
+            [Err(FastqError::Read(FormatError::Format { line: 4, .. }))] => {}
+            y => panic!("expected a truncated record, got {:?}", y),
+        }
+    }
+
+    #[test]
+    fn read_past_bad_records() {
+        let x = read(
+            "@a\nACXT\n+\nIIII\n@b\nAC\n+\nI\n@c\nAC\n+\nI\u{7f}\n@d\nGT\n+\nII\n",
+            Encoding::Phred33,
+        );
+        assert_eq!(x.len(), 4);
+        match &x[0] {
+            Err(FastqError::Parse { line: 2, .. }) => {}
+            y => panic!("expected a parse error, got {:?}", y),
+        }
+        match &x[1] {
+            Err(FastqError::Length { line: 8, .. }) => {}
+            y => panic!("expected a length error, got {:?}", y),
+        }
+        match &x[2] {
+            Err(FastqError::Quality { line: 12, .. }) => {}
+            y => panic!("expected a quality error, got {:?}", y),
+        }
+        assert_eq!(x[3].as_ref().unwrap().id, "d");
+
+        match &read("@a\nACGT\nIIII\n@b\nAC\n+\nII\n", Encoding::Phred33)[..] {
+            [Err(FastqError::Read(FormatError::Format { line: 3, .. }))] => {}
+            y => panic!("expected the stream to end at a format error, got {:?}", y),
+        }
+    }
+
+    #[test]
+    fn read_n() {
+        let x = read("@r\nANnT\n+\nI!!I\n", Encoding::Phred33);
+        let s = &x[0].as_ref().unwrap().seq;
+        let c = IupacDNACat::new();
+        assert_eq!(
+            s.strand(),
+            &c.polymer_from_string(String::from("ANNT")).unwrap().strand
+        );
+        assert_eq!(s.get_scored(1), Some((c.n.clone(), 0)));
+
+        let mut w = Writer::new(Vec::new(), Encoding::Phred33);
+        w.write(x[0].as_ref().unwrap()).unwrap();
+        assert_eq!(w.into_inner(), b"@r\nANNT\n+\nI!!I\n");
+    }
+
+    #[test]
+    fn inverse_reverses_quality() {
+        let x = read("@r\nGATC\n+\n!+5?\n", Encoding::Phred33);
+        let s = &x[0].as_ref().unwrap().seq;
+        let c = IupacDNACat::new();
+
+        let y = s.inverse();
+        assert_eq!(
+            y.strand(),
+            &c.polymer_from_string(String::from("gatc")).unwrap().strand
+        );
+        assert_eq!(y.quality(), &[30, 20, 10, 0]);
+        assert_eq!(y.inverse(), *s);
+
+        let z = s.subsequence(1..3).unwrap();
+        assert_eq!(z.quality(), &[10, 20]);
+        assert_eq!(z.get_scored(1), Some((c.t.clone(), 20)));
+    }
+
+    #[test]
+    fn write_round_trip() {
+        let records: Vec<Record<QualityStrand<IupacDNACell>>> = read(INPUT, Encoding::Phred33)
+            .into_iter()
+            .collect::<Result<_, _>>()
+            .unwrap();
+
+        let mut w = Writer::new(Vec::new(), Encoding::Phred64);
+        for r in records.iter() {
+            w.write(r).unwrap();
+        }
+        let out = String::from_utf8(w.into_inner()).unwrap();
+        assert_eq!(
+            out,
+            "@read1 lane 1\nGATTACA\n+\nhhTB@Jh\n@read2\nAC\n+\nEF\n"
+        );
+
+        let again: Vec<Record<QualityStrand<IupacDNACell>>> = read(&out, Encoding::Phred64)
+            .into_iter()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(again, records);
+        assert_eq!(Encoding::Phred64.encode(90), b'~');
+    }
+}
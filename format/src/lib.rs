@@ -0,0 +1,178 @@
+use std::error::Error;
+use std::fmt;
+use std::io::{self, BufRead};
+
+// NOTE: The error of the line based text formats. Readers with errors of
+// their own wrap it, the rest use it as is.
+#[derive(Debug)]
+pub enum FormatError {
+    Io(io::Error),
+    // A 1-based line, and the 1-based column of it, that does not parse.
+    Format {
+        line: usize,
+        column: usize,
+        expected: &'static str,
+    },
+}
+
+impl fmt::Display for FormatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FormatError::Io(e) => write!(f, "{}", e),
+            FormatError::Format {
+                line,
+                column,
+                expected,
+            } => write!(f, "line {} column {}: expected {}", line, column, expected),
+        }
+    }
+}
+
+impl Error for FormatError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            FormatError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for FormatError {
+    fn from(e: io::Error) -> Self {
+        FormatError::Io(e)
+    }
+}
+
+// The strand an annotation lies on, from its '+', '-' or '.' column.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Sense {
+    Forward,
+    Reverse,
+    Unknown,
+}
+
+// NOTE: Numbered lines of a text file, 1-based, with the line ending
+// trimmed. The first io error ends it, and stop ends it early, for readers
+// that end their stream at their first format error.
+pub struct Lines<R> {
+    inner: R,
+    line: usize,
+    done: bool,
+}
+
+impl<R: BufRead> Lines<R> {
+    pub fn new(inner: R) -> Self {
+        Lines {
+            inner,
+            line: 0,
+            done: false,
+        }
+    }
+
+    // The number of the last line read.
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    pub fn stop(&mut self) {
+        self.done = true;
+    }
+
+    // To look ahead with fill_buf, as a header ends.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    // NOTE: Reads the next line into buf as raw bytes, for formats that
+    // need not be UTF-8, with the line ending trimmed as by next. Returns
+    // the bytes taken from inner, line ending included, 0 at the end.
+    pub fn read_bytes(&mut self, buf: &mut Vec<u8>) -> io::Result<usize> {
+        buf.clear();
+        if self.done {
+            return Ok(0);
+        }
+
+        match self.inner.read_until(b'\n', buf) {
+            Ok(0) => {
+                self.done = true;
+                Ok(0)
+            }
+            Ok(n) => {
+                self.line += 1;
+                while let Some(b'\r') | Some(b'\n') = buf.last() {
+                    buf.pop();
+                }
+                Ok(n)
+            }
+            Err(e) => {
+                self.done = true;
+                Err(e)
+            }
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for Lines<R> {
+    type Item = io::Result<(usize, String)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let mut buf = String::new();
+        match self.inner.read_line(&mut buf) {
+            Ok(0) => {
+                self.done = true;
+                None
+            }
+            Ok(_) => {
+                self.line += 1;
+                let n = buf.trim_end_matches(&['\r', '\n'][..]).len();
+                buf.truncate(n);
+                Some(Ok((self.line, buf)))
+            }
+            Err(e) => {
+                self.done = true;
+                Some(Err(e))
+            }
+        }
+    }
+}
+
+// Drops the whitespace that ends a line, such as padding after sequence.
+pub fn trim(b: &[u8]) -> &[u8] {
+    let mut i = b.len();
+    while i > 0 && b[i - 1].is_ascii_whitespace() {
+        i -= 1;
+    }
+
+    &b[..i]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lines() {
+        let mut x = Lines::new("a\tb\r\n\nc  ".as_bytes());
+        assert_eq!(x.next().unwrap().unwrap(), (1, String::from("a\tb")));
+        assert_eq!(x.next().unwrap().unwrap(), (2, String::new()));
+
+        let mut buf = Vec::new();
+        assert_eq!(x.read_bytes(&mut buf).unwrap(), 3);
+        assert_eq!(
+            (x.line(), &buf[..], trim(&buf)),
+            (3, &b"c  "[..], &b"c"[..])
+        );
+        assert_eq!(x.read_bytes(&mut buf).unwrap(), 0);
+        assert!(x.next().is_none());
+
+        let mut y = Lines::new("a\nb\n".as_bytes());
+        y.next();
+        y.stop();
+        assert!(y.next().is_none());
+        assert_eq!(y.line(), 1);
+    }
+}
@@ -0,0 +1,840 @@
+use category::{Cat, ICat};
+use dna::{DNACat, DNACell};
+pub use format::FormatError;
+use format::Lines;
+use monomer::{IMono, ParseError};
+use polymer::{Helix, IPolymer, Polymer};
+use std::error::Error;
+use std::fmt;
+use std::io::{self, BufRead, Write};
+
+// Columns of the feature table: keys start at 5, everything else at 21.
+const KEY_COLUMN: usize = 5;
+const VALUE_COLUMN: usize = 21;
+const LINE_WIDTH: usize = 79;
+
+#[derive(Debug)]
+pub enum GenBankError {
+    // Reading failed, or a line is not what the record layout expects there.
+    Read(FormatError),
+    // A feature location this parser does not understand.
+    Location { line: usize, found: String },
+    // A sequence line that does not parse, the error's offset is the column.
+    Parse { line: usize, error: ParseError },
+}
+
+impl fmt::Display for GenBankError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GenBankError::Read(e) => write!(f, "{}", e),
+            GenBankError::Location { line, found } => {
+                write!(f, "line {}: unsupported location {:?}", line, found)
+            }
+            GenBankError::Parse { line, error } => write!(f, "line {}: {}", line, error),
+        }
+    }
+}
+
+impl Error for GenBankError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            GenBankError::Read(e) => Some(e),
+            GenBankError::Parse { error, .. } => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<FormatError> for GenBankError {
+    fn from(e: FormatError) -> Self {
+        GenBankError::Read(e)
+    }
+}
+
+impl From<io::Error> for GenBankError {
+    fn from(e: io::Error) -> Self {
+        GenBankError::Read(e.into())
+    }
+}
+
+fn layout(line: usize, column: usize, expected: &'static str) -> GenBankError {
+    GenBankError::Read(FormatError::Format {
+        line,
+        column,
+        expected,
+    })
+}
+
+// NOTE: 1-based, as written in the file. Before is a '<' bound and After
+// a '>' one, the feature runs on past the given base in that direction.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Bound {
+    Exact(usize),
+    Before(usize),
+    After(usize),
+}
+
+impl Bound {
+    pub fn pos(&self) -> usize {
+        match self {
+            Bound::Exact(x) | Bound::Before(x) | Bound::After(x) => *x,
+        }
+    }
+}
+
+impl fmt::Display for Bound {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Bound::Exact(x) => write!(f, "{}", x),
+            Bound::Before(x) => write!(f, "<{}", x),
+            Bound::After(x) => write!(f, ">{}", x),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum Location {
+    // Inclusive on both ends, a single base has start == end.
+    Range { start: Bound, end: Bound },
+    Complement(Box<Location>),
+    Join(Vec<Location>),
+}
+
+impl Location {
+    pub fn parse(s: &str) -> Option<Location> {
+        let b: Vec<u8> = s.bytes().filter(|x| !x.is_ascii_whitespace()).collect();
+        let mut i = 0;
+        let x = location(&b, &mut i)?;
+
+        match i == b.len() {
+            true => Some(x),
+            _ => None,
+        }
+    }
+
+    // NOTE: Joins are read in the order given, and a complement is the
+    // reverse complement of what it wraps, so the result reads 5' to 3' along
+    // the feature. None if the location runs off the end of seq.
+    pub fn extract<T, U, C>(&self, seq: &U, cat: &C) -> Option<U>
+    where
+        T: IMono,
+        U: IPolymer<T>,
+        C: ICat<T, U>,
+    {
+        match self {
+            Location::Range { start, end } => {
+                seq.subsequence(start.pos().checked_sub(1)?..end.pos())
+            }
+            Location::Complement(x) => Some(cat.inverse_p(&x.extract(seq, cat)?)),
+            Location::Join(xs) => {
+                let mut y = U::new();
+                for x in xs.iter() {
+                    y.concat(&mut x.extract(seq, cat)?);
+                }
+
+                Some(y)
+            }
+        }
+    }
+}
+
+fn eat(b: &[u8], i: &mut usize, s: &[u8]) -> bool {
+    match b[*i..].starts_with(s) {
+        true => {
+            *i += s.len();
+            true
+        }
+        _ => false,
+    }
+}
+
+fn bound(b: &[u8], i: &mut usize) -> Option<Bound> {
+    let f = match (eat(b, i, b"<"), eat(b, i, b">")) {
+        (true, _) => Bound::Before,
+        (_, true) => Bound::After,
+        _ => Bound::Exact,
+    };
+
+    let start = *i;
+    while *i < b.len() && b[*i].is_ascii_digit() {
+        *i += 1;
+    }
+
+    match std::str::from_utf8(&b[start..*i]).ok()?.parse() {
+        Ok(0) | Err(_) => None,
+        Ok(x) => Some(f(x)),
+    }
+}
+
+fn location(b: &[u8], i: &mut usize) -> Option<Location> {
+    if eat(b, i, b"complement(") {
+        let x = location(b, i)?;
+        return match eat(b, i, b")") {
+            true => Some(Location::Complement(Box::new(x))),
+            _ => None,
+        };
+    }
+
+    if eat(b, i, b"join(") {
+        let mut xs = vec![location(b, i)?];
+        while eat(b, i, b",") {
+            xs.push(location(b, i)?);
+        }
+        return match eat(b, i, b")") {
+            true => Some(Location::Join(xs)),
+            _ => None,
+        };
+    }
+
+    let start = bound(b, i)?;
+    let end = match eat(b, i, b"..") {
+        true => bound(b, i)?,
+        _ => start,
+    };
+
+    Some(Location::Range { start, end })
+}
+
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Location::Range { start, end } if start == end => write!(f, "{}", start),
+            Location::Range { start, end } => write!(f, "{}..{}", start, end),
+            Location::Complement(x) => write!(f, "complement({})", x),
+            Location::Join(xs) => {
+                write!(f, "join(")?;
+                for (i, x) in xs.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{}", x)?;
+                }
+                write!(f, ")")
+            }
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum Kind {
+    CDS,
+    Gene,
+    MRNA,
+    MiscFeature,
+    // Any other key, kept so the table writes back as it was read.
+    Other(String),
+}
+
+impl Kind {
+    pub fn from_key(s: &str) -> Kind {
+        match s {
+            "CDS" => Kind::CDS,
+            "gene" => Kind::Gene,
+            "mRNA" => Kind::MRNA,
+            "misc_feature" => Kind::MiscFeature,
+            x => Kind::Other(x.to_string()),
+        }
+    }
+
+    pub fn key(&self) -> &str {
+        match self {
+            Kind::CDS => "CDS",
+            Kind::Gene => "gene",
+            Kind::MRNA => "mRNA",
+            Kind::MiscFeature => "misc_feature",
+            Kind::Other(x) => x,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Feature {
+    pub kind: Kind,
+    pub location: Location,
+    // In file order, quotes removed. Flags such as /pseudo have no value.
+    pub qualifiers: Vec<(String, Option<String>)>,
+}
+
+impl Feature {
+    pub fn qualifier(&self, key: &str) -> Option<&str> {
+        self.qualifiers
+            .iter()
+            .find(|(k, _)| k == key)
+            .and_then(|(_, v)| v.as_deref())
+    }
+
+    pub fn extract(&self, seq: &Helix<DNACell>, cat: &DNACat) -> Option<Helix<DNACell>> {
+        self.location.extract(seq, cat)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct GenBank {
+    // Every line from LOCUS up to FEATURES, as read.
+    pub header: Vec<String>,
+    pub features: Vec<Feature>,
+    pub seq: Helix<DNACell>,
+}
+
+impl GenBank {
+    // The locus name, the first word after LOCUS.
+    pub fn name(&self) -> Option<&str> {
+        self.header
+            .iter()
+            .find(|x| x.starts_with("LOCUS"))
+            .and_then(|x| x.split_whitespace().nth(1))
+    }
+
+    pub fn feature_seq(&self, f: &Feature, cat: &DNACat) -> Option<Helix<DNACell>> {
+        f.extract(&self.seq, cat)
+    }
+}
+
+fn unquote(s: &str) -> String {
+    match s.len() >= 2 && s.starts_with('"') && s.ends_with('"') {
+        true => s[1..s.len() - 1].replace("\"\"", "\""),
+        _ => s.to_string(),
+    }
+}
+
+// An opening quote without its closing one yet, "" being an escaped quote.
+fn open_quote(s: &str) -> bool {
+    s.starts_with('"') && s.matches('"').count() % 2 == 1
+}
+
+struct Pending {
+    key: String,
+    location: String,
+    line: usize,
+    qualifiers: Vec<(String, Option<String>)>,
+}
+
+impl Pending {
+    fn finish(self) -> Result<Feature, GenBankError> {
+        let location = match Location::parse(&self.location) {
+            Some(x) => x,
+            None => {
+                return Err(GenBankError::Location {
+                    line: self.line,
+                    found: self.location,
+                })
+            }
+        };
+
+        Ok(Feature {
+            kind: Kind::from_key(&self.key),
+            location,
+            qualifiers: self
+                .qualifiers
+                .into_iter()
+                .map(|(k, v)| (k, v.map(|x| unquote(&x))))
+                .collect(),
+        })
+    }
+
+    fn continue_with(&mut self, s: &str) {
+        match self.qualifiers.last_mut() {
+            None => self.location.push_str(s),
+            Some((k, Some(v))) => {
+                if k != "translation" {
+                    v.push(' ');
+                }
+                v.push_str(s);
+            }
+            Some((_, v)) => *v = Some(s.to_string()),
+        }
+    }
+}
+
+#[derive(PartialEq)]
+enum Section {
+    Header,
+    Features,
+    Skip,
+    Origin,
+}
+
+// NOTE: Streams the records of a file, each ending at a "//" line. Only
+// the FEATURES table and ORIGIN are interpreted, other keywords are kept
+// as raw header lines. A bad record is an error and reading goes on after
+// its "//" line, only an io error ends the stream.
+pub struct Reader<'a, R> {
+    lines: Lines<R>,
+    cat: &'a DNACat,
+    // Whether the last record read reached its "//" line.
+    ended: bool,
+}
+
+impl<'a, R: BufRead> Reader<'a, R> {
+    pub fn new(inner: R, cat: &'a DNACat) -> Self {
+        Reader {
+            lines: Lines::new(inner),
+            cat,
+            ended: true,
+        }
+    }
+
+    // Drops what is left of a bad record.
+    fn skip(&mut self) {
+        for x in self.lines.by_ref() {
+            match x {
+                Ok((_, l)) if !l.starts_with("//") => {}
+                _ => break,
+            }
+        }
+    }
+
+    fn origin(&self, l: &str, seq: &mut Helix<DNACell>) -> Result<(), GenBankError> {
+        let mut i = 0;
+        for chunk in l.split(' ') {
+            if !chunk.is_empty() && !chunk.bytes().all(|x| x.is_ascii_digit()) {
+                match self.cat.polymer_from_bytes(chunk.as_bytes()) {
+                    Ok(mut x) => seq.concat(&mut x),
+                    Err(e) => {
+                        return Err(GenBankError::Parse {
+                            line: self.lines.line(),
+                            error: e.offset_by(i),
+                        })
+                    }
+                }
+            }
+            i += chunk.len() + 1;
+        }
+
+        Ok(())
+    }
+
+    fn record(&mut self) -> Result<Option<GenBank>, GenBankError> {
+        self.ended = false;
+        let mut header = Vec::new();
+        let mut features = Vec::new();
+        let mut seq = Helix::new();
+        let mut pending: Option<Pending> = None;
+        let mut section = Section::Header;
+
+        loop {
+            let buf = match self.lines.next() {
+                Some(x) => x?.1,
+                None => {
+                    return match header.is_empty() && section == Section::Header {
+                        true => Ok(None),
+                        _ => Err(layout(self.lines.line() + 1, 1, "\"//\" to end the record")),
+                    }
+                }
+            };
+
+            let l = buf.trim_end();
+            if l.is_empty() {
+                continue;
+            }
+            if l.starts_with("//") {
+                self.ended = true;
+                break;
+            }
+
+            let keyword = !l.starts_with(' ');
+            if keyword {
+                if let Some(x) = pending.take() {
+                    features.push(x.finish()?);
+                }
+
+                section = match l.split_whitespace().next() {
+                    Some("FEATURES") => Section::Features,
+                    Some("ORIGIN") => Section::Origin,
+                    _ if section == Section::Header => Section::Header,
+                    _ => Section::Skip,
+                };
+            }
+
+            match section {
+                Section::Header => {
+                    if header.is_empty() && !l.starts_with("LOCUS") {
+                        return Err(layout(self.lines.line(), 1, "a LOCUS line"));
+                    }
+                    header.push(l.to_string());
+                }
+                Section::Features if !keyword => {
+                    let key = l.get(KEY_COLUMN..VALUE_COLUMN).unwrap_or("").trim();
+                    let value = l.get(VALUE_COLUMN..).unwrap_or("").trim();
+
+                    if !key.is_empty() {
+                        if let Some(x) = pending.take() {
+                            features.push(x.finish()?);
+                        }
+                        pending = Some(Pending {
+                            key: key.to_string(),
+                            location: value.to_string(),
+                            line: self.lines.line(),
+                            qualifiers: Vec::new(),
+                        });
+                        continue;
+                    }
+
+                    let x = match pending.as_mut() {
+                        Some(x) => x,
+                        None => {
+                            return Err(layout(self.lines.line(), KEY_COLUMN + 1, "a feature key"))
+                        }
+                    };
+
+                    let in_quote = match x.qualifiers.last() {
+                        Some((_, Some(v))) => open_quote(v),
+                        _ => false,
+                    };
+
+                    match (value.strip_prefix('/'), in_quote) {
+                        (Some(q), false) => {
+                            let (k, v) = match q.split_once('=') {
+                                Some((k, v)) => (k.to_string(), Some(v.to_string())),
+                                None => (q.to_string(), None),
+                            };
+                            x.qualifiers.push((k, v));
+                        }
+                        _ => x.continue_with(value),
+                    }
+                }
+                Section::Origin if !keyword => self.origin(l, &mut seq)?,
+                _ => {}
+            }
+        }
+
+        if let Some(x) = pending.take() {
+            features.push(x.finish()?);
+        }
+
+        Ok(Some(GenBank {
+            header,
+            features,
+            seq,
+        }))
+    }
+}
+
+impl<'a, R: BufRead> Iterator for Reader<'a, R> {
+    type Item = Result<GenBank, GenBankError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.record() {
+            Ok(x) => x.map(Ok),
+            Err(e) => {
+                if !self.ended {
+                    self.skip();
+                }
+                Some(Err(e))
+            }
+        }
+    }
+}
+
+// Rewrites the "N bp" of a LOCUS line for a sequence of len, keeping the
+// number right aligned where it was.
+fn locus_line(l: &str, len: usize) -> String {
+    let i = match l.find(" bp") {
+        Some(i) => i,
+        None => return l.to_string(),
+    };
+
+    let digits = l[..i].trim_end_matches(|x: char| x.is_ascii_digit());
+    let start = digits.trim_end_matches(' ').len();
+    let width = (i - start).saturating_sub(1);
+
+    format!("{} {:>w$}{}", &l[..start], len, &l[i..], w = width)
+}
+
+// Splits s into lines of at most width bytes, breaking after the last
+// at byte that fits. Without one, breaks at width if hard, else runs over.
+fn wrap(s: &str, width: usize, at: u8, hard: bool) -> Vec<&str> {
+    let mut lines = Vec::new();
+    let mut rest = s;
+
+    while rest.len() > width {
+        let b = rest.as_bytes();
+        let cut = match b[..=width].iter().rposition(|x| *x == at) {
+            Some(i) if at == b' ' => (i, i + 1),
+            Some(i) => (i + 1, i + 1),
+            None if hard => (width, width),
+            None => match b.iter().position(|x| *x == at) {
+                Some(i) => (i, i + 1),
+                None => break,
+            },
+        };
+
+        lines.push(&rest[..cut.0]);
+        rest = &rest[cut.1..];
+    }
+
+    lines.push(rest);
+    lines
+}
+
+pub struct Writer<W> {
+    inner: W,
+}
+
+impl<W: Write> Writer<W> {
+    pub fn new(inner: W) -> Self {
+        Writer { inner }
+    }
+
+    fn table_lines(&mut self, key: &str, lines: Vec<&str>) -> io::Result<()> {
+        for (i, x) in lines.iter().enumerate() {
+            let k = match i {
+                0 => key,
+                _ => "",
+            };
+            writeln!(
+                self.inner,
+                "{:k$}{:v$}{}",
+                "",
+                k,
+                x,
+                k = KEY_COLUMN,
+                v = VALUE_COLUMN - KEY_COLUMN
+            )?;
+        }
+
+        Ok(())
+    }
+
+    // NOTE: Qualifier values are quoted unless they are a plain number,
+    // such as /codon_start=1.
+    pub fn write(&mut self, g: &GenBank) -> io::Result<()> {
+        let width = LINE_WIDTH - VALUE_COLUMN;
+
+        for l in g.header.iter() {
+            match l.starts_with("LOCUS") {
+                true => writeln!(self.inner, "{}", locus_line(l, g.seq.len()))?,
+                _ => writeln!(self.inner, "{}", l)?,
+            }
+        }
+
+        writeln!(
+            self.inner,
+            "{:v$}Location/Qualifiers",
+            "FEATURES",
+            v = VALUE_COLUMN
+        )?;
+        for f in g.features.iter() {
+            let location = f.location.to_string();
+            self.table_lines(f.kind.key(), wrap(&location, width, b',', true))?;
+
+            for (k, v) in f.qualifiers.iter() {
+                let q = match v {
+                    None => format!("/{}", k),
+                    Some(x) if !x.is_empty() && x.bytes().all(|y| y.is_ascii_digit()) => {
+                        format!("/{}={}", k, x)
+                    }
+                    Some(x) => format!("/{}=\"{}\"", k, x.replace('"', "\"\"")),
+                };
+
+                let lines = match k == "translation" {
+                    true => wrap(&q, width, 0, true),
+                    _ => wrap(&q, width, b' ', false),
+                };
+                self.table_lines("", lines)?;
+            }
+        }
+
+        writeln!(self.inner, "ORIGIN")?;
+        let bases: Vec<String> = g.seq.iter().map(|x| x.to_string().to_lowercase()).collect();
+        for (i, line) in bases.chunks(60).enumerate() {
+            write!(self.inner, "{:>9}", i * 60 + 1)?;
+            for group in line.chunks(10) {
+                write!(self.inner, " {}", group.concat())?;
+            }
+            writeln!(self.inner)?;
+        }
+
+        writeln!(self.inner, "//")
+    }
+
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "\
+LOCUS       TEST01                    40 bp    DNA     circular SYN 01-JAN-2020
+DEFINITION  A test construct.
+ACCESSION   TEST01
+FEATURES             Location/Qualifiers
+     source          1..40
+                     /organism=\"synthetic construct\"
+     gene            <3..>14
+                     /gene=\"abc\"
+     CDS             join(3..8,
+                     12..14)
+                     /gene=\"abc\"
+                     /codon_start=1
+                     /note=\"a \"\"quoted\"\" note that runs onto
+                     /a second line\"
+                     /translation=\"MA
+                     Q\"
+                     /pseudo
+     misc_feature    complement(20..28)
+                     /label=rev
+BASE COUNT       10 a     10 c     10 g     10 t
+ORIGIN
+        1 ggatgccagt aactgcaaat tccggaatta cgtacgtacg
+//
+";
+
+    fn read(input: &str) -> Vec<Result<GenBank, GenBankError>> {
+        let c = DNACat::new();
+        Reader::new(input.as_bytes(), &c).collect()
+    }
+
+    #[test]
+    fn locations() {
+        let x = Location::parse("complement(join(<1..20, 30..>45))").unwrap();
+        assert_eq!(
+            x,
+            Location::Complement(Box::new(Location::Join(vec![
+                Location::Range {
+                    start: Bound::Before(1),
+                    end: Bound::Exact(20),
+                },
+                Location::Range {
+                    start: Bound::Exact(30),
+                    end: Bound::After(45),
+                },
+            ])))
+        );
+        assert_eq!(x.to_string(), "complement(join(<1..20,30..>45))");
+        assert_eq!(Location::parse("467").unwrap().to_string(), "467");
+
+        assert_eq!(Location::parse("join(1..2"), None);
+        assert_eq!(Location::parse("0..3"), None);
+        assert_eq!(Location::parse("J00194.1:100..202"), None);
+    }
+
+    #[test]
+    fn read_record() {
+        let x = read(INPUT);
+        assert_eq!(x.len(), 1);
+        let g = x[0].as_ref().unwrap();
+
+        assert_eq!(g.name(), Some("TEST01"));
+        assert_eq!(g.header.len(), 3);
+        assert_eq!(g.seq.len(), 40);
+        assert_eq!(g.features.len(), 4);
+
+        let cds = &g.features[2];
+        assert_eq!(cds.kind, Kind::CDS);
+        assert_eq!(cds.location.to_string(), "join(3..8,12..14)");
+        assert_eq!(cds.qualifier("codon_start"), Some("1"));
+        assert_eq!(
+            cds.qualifier("note"),
+            Some("a \"quoted\" note that runs onto /a second line")
+        );
+        assert_eq!(cds.qualifier("translation"), Some("MAQ"));
+        assert_eq!(
+            cds.qualifiers.last().unwrap(),
+            &(String::from("pseudo"), None)
+        );
+
+        assert_eq!(g.features[0].kind, Kind::Other(String::from("source")));
+        assert_eq!(g.features[1].kind, Kind::Gene);
+        assert_eq!(g.features[3].kind, Kind::MiscFeature);
+        assert_eq!(g.features[3].qualifier("label"), Some("rev"));
+    }
+
+    #[test]
+    fn extract_features() {
+        let c = DNACat::new();
+        let x = read(INPUT);
+        let g = x[0].as_ref().unwrap();
+
+        assert_eq!(
+            g.feature_seq(&g.features[2], &c).unwrap(),
+            c.polymer_from_string(String::from("atgccaact")).unwrap()
+        );
+        assert_eq!(
+            g.feature_seq(&g.features[3], &c).unwrap(),
+            c.polymer_from_string(String::from("attccggaa")).unwrap()
+        );
+
+        let off_end = Location::parse("35..41").unwrap();
+        assert_eq!(off_end.extract(&g.seq, &c), None);
+    }
+
+    #[test]
+    fn read_errors() {
+        match &read("DEFINITION  x\n//\n")[..] {
+            [Err(GenBankError::Read(FormatError::Format { line: 1, .. }))] => {}
+            y => panic!("expected a format error, got {:?}", y),
+        }
+        match &read("LOCUS       X\nFEATURES             Location/Qualifiers\n     gene            order(1..2)\n//\n")[..] {
+            [Err(GenBankError::Location { line: 3, .. })] => {}
+            y => panic!("expected a location error, got {:?}", y),
+        }
+        match &read("LOCUS       X\nORIGIN\n        1 gatxaca\n//\n")[..] {
+            [Err(GenBankError::Parse { line: 3, error })] => {
+                assert_eq!(
+                    error,
+                    &ParseError::monomer(String::from("x"), "DNA (ACGT)").offset_by(13)
+                )
+            }
+            y => panic!("expected a parse error, got {:?}", y),
+        }
+        match &read("LOCUS       X\nORIGIN\n        1 gat\n")[..] {
+            [Err(GenBankError::Read(FormatError::Format { line: 4, .. }))] => {}
+            y => panic!("expected a missing terminator, got {:?}", y),
+        }
+
+        // Reading goes on with the record after a bad one, whether the
+        // error came before its "//" or at it.
+        let x = read(concat!(
+            "LOCUS       X\nORIGIN\n        1 gatxaca\n        8 gat\n//\n",
+            "LOCUS       Y\nFEATURES             Location/Qualifiers\n",
+            "     gene            order(1..2)\n//\n",
+            "LOCUS       Z\n//\n",
+        ));
+        assert_eq!(x.len(), 3);
+        match &x[0] {
+            Err(GenBankError::Parse { line: 3, .. }) => {}
+            y => panic!("expected a parse error, got {:?}", y),
+        }
+        match &x[1] {
+            Err(GenBankError::Location { line: 8, .. }) => {}
+            y => panic!("expected a location error, got {:?}", y),
+        }
+        assert_eq!(x[2].as_ref().unwrap().header, vec!["LOCUS       Z"]);
+    }
+
+    #[test]
+    fn write_round_trip() {
+        let c = DNACat::new();
+        let mut x = read(INPUT);
+        let mut g = x.remove(0).unwrap();
+
+        g.seq
+            .concat(&mut c.polymer_from_string("acgt".repeat(6)).unwrap());
+        g.features[2]
+            .qualifiers
+            .push((String::from("translation"), Some("MKV".repeat(30))));
+
+        let mut w = Writer::new(Vec::new());
+        w.write(&g).unwrap();
+        let out = String::from_utf8(w.into_inner()).unwrap();
+
+        assert!(out.starts_with(
+            "LOCUS       TEST01                    64 bp    DNA     circular SYN 01-JAN-2020\n"
+        ));
+        assert!(out.contains("     CDS             join(3..8,12..14)\n"));
+        assert!(out.contains("                     /codon_start=1\n"));
+        assert!(out.contains("       61 acgt\n//\n"));
+        assert!(out
+            .lines()
+            .all(|l| l.len() <= LINE_WIDTH || l.starts_with("LOCUS")));
+
+        let again = read(&out).remove(0).unwrap();
+        assert_eq!(again.header[1..], g.header[1..]);
+        assert_eq!(again.features, g.features);
+        assert_eq!(again.seq, g.seq);
+    }
+}
@@ -0,0 +1,671 @@
+use amino::AminoCell;
+use category::{ICat, Intern};
+use dna::{DNACat, DNACell};
+use format::Lines;
+pub use format::{FormatError, Sense};
+use polymer::{Helix, Polymer, Strand};
+use ribosome::Ribosome;
+use rna::{RNACat, RNACell};
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use std::fmt;
+use std::io::{self, BufRead};
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Format {
+    // key=value;key=value, with ID and Parent linking features.
+    GFF3,
+    // key "value"; key "value";, linked through gene_id and transcript_id.
+    GTF,
+}
+
+#[derive(Debug)]
+pub enum GffError {
+    Read(FormatError),
+    // A feature naming a parent that no feature has as its id.
+    MissingParent { id: Option<String>, parent: String },
+}
+
+impl fmt::Display for GffError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GffError::Read(e) => write!(f, "{}", e),
+            GffError::MissingParent { id, parent } => match id {
+                Some(x) => write!(f, "{} has a missing parent {}", x, parent),
+                None => write!(f, "missing parent {}", parent),
+            },
+        }
+    }
+}
+
+impl Error for GffError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            GffError::Read(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<FormatError> for GffError {
+    fn from(e: FormatError) -> Self {
+        GffError::Read(e)
+    }
+}
+
+impl From<io::Error> for GffError {
+    fn from(e: io::Error) -> Self {
+        GffError::Read(e.into())
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Feature {
+    pub seqid: String,
+    pub source: String,
+    pub kind: String,
+    // 1-based and inclusive, as in the file.
+    pub start: usize,
+    pub end: usize,
+    pub score: Option<f64>,
+    pub sense: Sense,
+    // Bases to skip from the 5' end to reach the first whole codon.
+    pub phase: Option<usize>,
+    // In file order, GTF keys that repeat collect all their values.
+    pub attributes: Vec<(String, Vec<String>)>,
+    // Filled from ID and Parent in GFF3, and gene_id and transcript_id
+    // in GTF, so both build the same tree.
+    pub id: Option<String>,
+    pub parents: Vec<String>,
+}
+
+impl Feature {
+    pub fn attribute(&self, key: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(k, _)| k == key)
+            .and_then(|(_, v)| v.first())
+            .map(|x| x.as_str())
+    }
+}
+
+fn percent_decode(s: &str) -> String {
+    let b = s.as_bytes();
+    let mut x = Vec::with_capacity(b.len());
+    let mut i = 0;
+
+    while i < b.len() {
+        let hex = b
+            .get(i + 1..i + 3)
+            .and_then(|h| std::str::from_utf8(h).ok());
+        match (b[i], hex.and_then(|h| u8::from_str_radix(h, 16).ok())) {
+            (b'%', Some(y)) => {
+                x.push(y);
+                i += 3;
+            }
+            (y, _) => {
+                x.push(y);
+                i += 1;
+            }
+        }
+    }
+
+    String::from_utf8_lossy(&x).into_owned()
+}
+
+fn push_attribute(attributes: &mut Vec<(String, Vec<String>)>, k: &str, v: String) {
+    match attributes.iter_mut().find(|(x, _)| x == k) {
+        Some((_, y)) => y.push(v),
+        None => attributes.push((k.to_string(), vec![v])),
+    }
+}
+
+fn gff3_attributes(s: &str) -> Option<Vec<(String, Vec<String>)>> {
+    let mut x = Vec::new();
+    for pair in s.split(';').map(str::trim).filter(|y| !y.is_empty()) {
+        let (k, v) = pair.split_once('=')?;
+        for y in v.split(',') {
+            push_attribute(&mut x, &percent_decode(k), percent_decode(y));
+        }
+    }
+
+    Some(x)
+}
+
+fn gtf_attributes(s: &str) -> Option<Vec<(String, Vec<String>)>> {
+    let mut x = Vec::new();
+    for pair in s.split(';').map(str::trim).filter(|y| !y.is_empty()) {
+        let (k, v) = pair.split_once(char::is_whitespace)?;
+        let v = v.trim();
+        let v = match v.len() >= 2 && v.starts_with('"') && v.ends_with('"') {
+            true => &v[1..v.len() - 1],
+            _ => v,
+        };
+        push_attribute(&mut x, k, v.to_string());
+    }
+
+    Some(x)
+}
+
+fn links(
+    format: Format,
+    kind: &str,
+    attributes: &[(String, Vec<String>)],
+) -> (Option<String>, Vec<String>) {
+    let get = |k: &str| {
+        attributes
+            .iter()
+            .find(|(x, _)| x == k)
+            .map(|(_, v)| v.clone())
+            .unwrap_or_default()
+    };
+
+    match format {
+        Format::GFF3 => (get("ID").into_iter().next(), get("Parent")),
+        Format::GTF => {
+            let gene = get("gene_id").into_iter().next();
+            let transcript = get("transcript_id").into_iter().next();
+            match kind {
+                "gene" => (gene, Vec::new()),
+                "transcript" => (transcript, gene.into_iter().collect()),
+                _ => (None, transcript.or(gene).into_iter().collect()),
+            }
+        }
+    }
+}
+
+fn feature(format: Format, l: &str, line: usize) -> Result<Feature, GffError> {
+    let columns: Vec<&str> = l.split('\t').collect();
+    let err = |column: usize, expected: &'static str| {
+        GffError::from(FormatError::Format {
+            line,
+            column,
+            expected,
+        })
+    };
+
+    if columns.len() != 9 {
+        return Err(err(columns.len().min(9), "nine tab separated columns"));
+    }
+
+    let start: usize = columns[3].parse().map_err(|_| err(4, "a start position"))?;
+    let end: usize = columns[4].parse().map_err(|_| err(5, "an end position"))?;
+    if start == 0 || end < start {
+        return Err(err(5, "1 <= start <= end"));
+    }
+
+    let score = match columns[5] {
+        "." => None,
+        x => Some(x.parse().map_err(|_| err(6, "a score or '.'"))?),
+    };
+
+    let sense = match columns[6] {
+        "+" => Sense::Forward,
+        "-" => Sense::Reverse,
+        "." | "?" => Sense::Unknown,
+        _ => return Err(err(7, "'+', '-', '.' or '?'")),
+    };
+
+    let phase = match columns[7] {
+        "." => None,
+        "0" => Some(0),
+        "1" => Some(1),
+        "2" => Some(2),
+        _ => return Err(err(8, "a phase of 0, 1, 2 or '.'")),
+    };
+
+    let attributes = match format {
+        Format::GFF3 => gff3_attributes(columns[8]),
+        Format::GTF => gtf_attributes(columns[8]),
+    }
+    .ok_or_else(|| err(9, "attributes"))?;
+
+    let (id, parents) = links(format, columns[2], &attributes);
+
+    Ok(Feature {
+        seqid: percent_decode(columns[0]),
+        source: columns[1].to_string(),
+        kind: columns[2].to_string(),
+        start,
+        end,
+        score,
+        sense,
+        phase,
+        attributes,
+        id,
+        parents,
+    })
+}
+
+// NOTE: Streams features line by line, skipping comments and stopping at
+// a GFF3 ##FASTA section. Each feature is a line of its own, so a bad one
+// is an error and reading goes on with the next, only an io error ends
+// the stream.
+pub struct Reader<R> {
+    lines: Lines<R>,
+    format: Format,
+}
+
+impl<R: BufRead> Reader<R> {
+    pub fn new(inner: R, format: Format) -> Self {
+        Reader {
+            lines: Lines::new(inner),
+            format,
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for Reader<R> {
+    type Item = Result<Feature, GffError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(x) = self.lines.next() {
+            let (n, l) = match x {
+                Ok(y) => y,
+                Err(e) => return Some(Err(e.into())),
+            };
+
+            if l.starts_with("##FASTA") {
+                self.lines.stop();
+                continue;
+            }
+            if l.trim().is_empty() || l.starts_with('#') {
+                continue;
+            }
+
+            return Some(feature(self.format, &l, n));
+        }
+
+        None
+    }
+}
+
+// NOTE: GTF 2.2 has no gene or transcript lines, only gene_id and
+// transcript_id on each exon and CDS. Where a feature's parent is one of
+// those ids and no feature has it, a gene or transcript is made for it,
+// spanning its children and placed before the first of them.
+fn implied(features: Vec<Feature>) -> Vec<Feature> {
+    let ids: HashSet<String> = features.iter().filter_map(|x| x.id.clone()).collect();
+    let missing = |x: &Feature, key: &str| {
+        x.attribute(key)
+            .filter(|v| x.parents.iter().any(|p| p == v) && !ids.contains(*v))
+            .map(String::from)
+    };
+
+    // The transcript and gene each feature implies.
+    let wants: Vec<(Option<String>, Option<String>)> = features
+        .iter()
+        .map(|x| match missing(x, "transcript_id") {
+            Some(t) => {
+                let g = x
+                    .attribute("gene_id")
+                    .filter(|g| !ids.contains(*g))
+                    .map(String::from);
+                (Some(t), g)
+            }
+            None => (None, missing(x, "gene_id")),
+        })
+        .collect();
+
+    let mut spans: HashMap<(&str, String), (usize, usize)> = HashMap::new();
+    for (x, (t, g)) in features.iter().zip(wants.iter()) {
+        let keys = t
+            .iter()
+            .map(|y| ("transcript", y))
+            .chain(g.iter().map(|y| ("gene", y)));
+        for (kind, y) in keys {
+            let z = spans.entry((kind, y.clone())).or_insert((x.start, x.end));
+            z.0 = z.0.min(x.start);
+            z.1 = z.1.max(x.end);
+        }
+    }
+
+    let make = |x: &Feature, kind: &'static str, id: &str, parents: Vec<String>| {
+        let (start, end) = spans[&(kind, id.to_string())];
+        let mut attributes = Vec::new();
+        if let Some(g) = x.attribute("gene_id") {
+            attributes.push((String::from("gene_id"), vec![g.to_string()]));
+        }
+        if kind == "transcript" {
+            attributes.push((String::from("transcript_id"), vec![id.to_string()]));
+        }
+
+        Feature {
+            seqid: x.seqid.clone(),
+            source: x.source.clone(),
+            kind: kind.to_string(),
+            start,
+            end,
+            score: None,
+            sense: x.sense,
+            phase: None,
+            attributes,
+            id: Some(id.to_string()),
+            parents,
+        }
+    };
+
+    let mut made = HashSet::new();
+    let mut out = Vec::with_capacity(features.len());
+    for (x, (t, g)) in features.into_iter().zip(wants) {
+        if let Some(g) = g {
+            if made.insert(("gene", g.clone())) {
+                out.push(make(&x, "gene", &g, Vec::new()));
+            }
+        }
+        if let Some(t) = t {
+            if made.insert(("transcript", t.clone())) {
+                let gene = x.attribute("gene_id").map(String::from);
+                out.push(make(&x, "transcript", &t, gene.into_iter().collect()));
+            }
+        }
+        out.push(x);
+    }
+
+    out
+}
+
+// NOTE: Features indexed by id, with each parent pointing at its children
+// in file order. GFF3 lets one id span several lines (a CDS split over
+// exons, say), children attach to the first of them.
+#[derive(Debug)]
+pub struct Annotation {
+    pub features: Vec<Feature>,
+    children: Vec<Vec<usize>>,
+    roots: Vec<usize>,
+    ids: HashMap<String, usize>,
+}
+
+impl Annotation {
+    pub fn new(features: Vec<Feature>) -> Result<Self, GffError> {
+        let features = implied(features);
+        let mut ids = HashMap::new();
+        for (i, x) in features.iter().enumerate() {
+            if let Some(id) = &x.id {
+                ids.entry(id.clone()).or_insert(i);
+            }
+        }
+
+        let mut children = vec![Vec::new(); features.len()];
+        let mut roots = Vec::new();
+        for (i, x) in features.iter().enumerate() {
+            if x.parents.is_empty() {
+                roots.push(i);
+            }
+
+            for p in x.parents.iter() {
+                match ids.get(p) {
+                    Some(j) => children[*j].push(i),
+                    None => {
+                        return Err(GffError::MissingParent {
+                            id: x.id.clone(),
+                            parent: p.clone(),
+                        })
+                    }
+                }
+            }
+        }
+
+        Ok(Annotation {
+            features,
+            children,
+            roots,
+            ids,
+        })
+    }
+
+    pub fn read<R: BufRead>(r: R, format: Format) -> Result<Self, GffError> {
+        Self::new(Reader::new(r, format).collect::<Result<_, _>>()?)
+    }
+
+    pub fn find(&self, id: &str) -> Option<usize> {
+        self.ids.get(id).cloned()
+    }
+
+    pub fn roots(&self) -> &[usize] {
+        &self.roots
+    }
+
+    pub fn children(&self, i: usize) -> &[usize] {
+        &self.children[i]
+    }
+
+    // Top level features annotating the sequence named seqid.
+    pub fn on<'a>(&'a self, seqid: &'a str) -> impl Iterator<Item = usize> + 'a {
+        self.roots
+            .iter()
+            .cloned()
+            .filter(move |i| self.features[*i].seqid == seqid)
+    }
+
+    // NOTE: Joins the CDS children of a transcript in genome order, then
+    // takes the reverse complement for a '-' transcript. The phase of the
+    // CDS at the 5' end is trimmed off. None if a CDS runs off seq.
+    pub fn splice(
+        &self,
+        transcript: usize,
+        seq: &Helix<DNACell>,
+        cat: &DNACat,
+    ) -> Option<Helix<DNACell>> {
+        let mut cds: Vec<&Feature> = self.children[transcript]
+            .iter()
+            .map(|i| &self.features[*i])
+            .filter(|x| x.kind == "CDS")
+            .collect();
+        cds.sort_by_key(|x| x.start);
+
+        let mut x = Helix::new();
+        for c in cds.iter() {
+            x.concat(&mut seq.subsequence(c.start - 1..c.end)?);
+        }
+
+        let (x, first) = match self.features[transcript].sense {
+            Sense::Reverse => (cat.inverse_p(&x), cds.last()),
+            _ => (x, cds.first()),
+        };
+
+        match first.and_then(|c| c.phase) {
+            Some(p) if p > 0 => x.subsequence(p.min(x.len())..),
+            _ => Some(x),
+        }
+    }
+
+    pub fn transcribe(
+        &self,
+        transcript: usize,
+        seq: &Helix<DNACell>,
+        dna_c: &DNACat,
+        rna_c: &RNACat,
+    ) -> Option<Strand<RNACell>> {
+        let x = self.splice(transcript, seq, dna_c)?;
+        Some(
+            x.iter()
+                .map(|y| rna_c.intern(&polymerase::dna_to_mrna(y.read().as_ref().unwrap().clone())))
+                .collect(),
+        )
+    }
+
+    // NOTE: A trailing partial codon is dropped, so CDS annotations that
+    // stop short of a whole codon still translate.
+    pub fn translate(
+        &self,
+        transcript: usize,
+        seq: &Helix<DNACell>,
+        dna_c: &DNACat,
+        ribo: &Ribosome,
+    ) -> Option<Strand<AminoCell>> {
+        let x = self.transcribe(transcript, seq, dna_c, &ribo.rna_c)?;
+        let whole = x.len() - x.len() % 3;
+        ribo.plain_translate(x.subsequence(..whole)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use amino::AminoCat;
+    use category::Cat;
+
+    const GENOME: &str = "CCCCTTAAACTCTGGACTTACTTCATGGGG";
+
+    const GFF3: &str = "\
+##gff-version 3
+# a reverse strand gene with one intron
+chr1\ttest\tgene\t5\t26\t.\t-\t.\tID=gene1;Name=abc%3Bdef
+chr1\ttest\tmRNA\t5\t26\t.\t-\t.\tID=tx1;Parent=gene1
+chr1\ttest\texon\t5\t11\t.\t-\t.\tParent=tx1
+chr1\ttest\texon\t22\t26\t.\t-\t.\tParent=tx1
+chr1\ttest\tCDS\t5\t11\t.\t-\t1\tID=cds1;Parent=tx1
+chr1\ttest\tCDS\t22\t26\t.\t-\t0\tID=cds1;Parent=tx1
+chr2\ttest\tgene\t1\t9\t0.5\t+\t.\tID=gene2
+
+##FASTA
+>chr1
+";
+
+    const GTF: &str = "\
+chr1\ttest\tgene\t5\t26\t.\t-\t.\tgene_id \"g1\";
+chr1\ttest\ttranscript\t5\t26\t.\t-\t.\tgene_id \"g1\"; transcript_id \"t1\"; tag \"basic\"; tag \"CCDS\";
+chr1\ttest\tCDS\t22\t26\t.\t-\t0\tgene_id \"g1\"; transcript_id \"t1\";
+chr1\ttest\tCDS\t5\t11\t.\t-\t1\tgene_id \"g1\"; transcript_id \"t1\";
+";
+
+    #[test]
+    fn read_gff3_tree() {
+        let a = Annotation::read(GFF3.as_bytes(), Format::GFF3).unwrap();
+
+        assert_eq!(a.features.len(), 7);
+        assert_eq!(a.roots(), &[0, 6]);
+        assert_eq!(a.on("chr1").collect::<Vec<usize>>(), vec![0]);
+        assert_eq!(a.features[0].attribute("Name"), Some("abc;def"));
+        assert_eq!(a.features[6].score, Some(0.5));
+
+        let tx = a.find("tx1").unwrap();
+        assert_eq!(a.children(a.find("gene1").unwrap()), &[tx]);
+        assert_eq!(a.children(tx), &[2, 3, 4, 5]);
+        assert_eq!(a.find("cds1"), Some(4));
+    }
+
+    #[test]
+    fn read_gtf_tree() {
+        let a = Annotation::read(GTF.as_bytes(), Format::GTF).unwrap();
+
+        assert_eq!(a.roots(), &[0]);
+        assert_eq!(a.children(0), &[1]);
+        assert_eq!(a.children(1), &[2, 3]);
+        assert_eq!(
+            a.features[1].attributes[2],
+            (
+                String::from("tag"),
+                vec![String::from("basic"), String::from("CCDS")]
+            )
+        );
+    }
+
+    #[test]
+    fn read_gtf22_tree() {
+        // No gene or transcript lines, as in GTF 2.2.
+        let gtf = GTF.lines().skip(2).collect::<Vec<&str>>().join("\n");
+        let a = Annotation::read(gtf.as_bytes(), Format::GTF).unwrap();
+
+        assert_eq!(a.features.len(), 4);
+        assert_eq!(a.roots(), &[0]);
+        assert_eq!(a.features[0].kind, "gene");
+        assert_eq!(a.find("g1"), Some(0));
+        assert_eq!(a.children(0), &[1]);
+        assert_eq!(a.find("t1"), Some(1));
+        assert_eq!(a.children(1), &[2, 3]);
+        assert_eq!((a.features[1].start, a.features[1].end), (5, 26));
+        assert_eq!(a.features[1].sense, Sense::Reverse);
+        assert_eq!(a.features[1].parents, vec![String::from("g1")]);
+
+        let dc = DNACat::new();
+        let genome = dc.polymer_from_string(String::from(GENOME)).unwrap();
+        assert_eq!(
+            a.splice(a.find("t1").unwrap(), &genome, &dc).unwrap(),
+            dc.polymer_from_string(String::from("atgaaagtttaa"))
+                .unwrap()
+        );
+
+        // A transcript line alone still gets its gene.
+        let a = Annotation::read(GTF.lines().nth(1).unwrap().as_bytes(), Format::GTF).unwrap();
+        assert_eq!(a.features.len(), 2);
+        assert_eq!(a.children(a.find("g1").unwrap()), &[1]);
+    }
+
+    #[test]
+    fn read_errors() {
+        let x: Vec<Result<Feature, GffError>> = Reader::new(
+            "chr1\tx\tgene\t5\t1\t.\t+\t.\tID=a\n".as_bytes(),
+            Format::GFF3,
+        )
+        .collect();
+        match &x[..] {
+            [Err(GffError::Read(FormatError::Format {
+                line: 1, column: 5, ..
+            }))] => {}
+            y => panic!("expected a format error, got {:?}", y),
+        }
+
+        let x: Vec<Result<Feature, GffError>> = Reader::new(
+            "#\nchr1\tx\tgene\t1\t5\t.\t*\t.\tID=a\n".as_bytes(),
+            Format::GFF3,
+        )
+        .collect();
+        match &x[..] {
+            [Err(GffError::Read(FormatError::Format {
+                line: 2, column: 7, ..
+            }))] => {}
+            y => panic!("expected a format error, got {:?}", y),
+        }
+
+        let x: Vec<Result<Feature, GffError>> = Reader::new(
+            "chr1\tx\tgene\t5\t1\t.\t+\t.\tID=a\nchr1\tx\tgene\t1\t5\t.\t+\t.\tID=b\n".as_bytes(),
+            Format::GFF3,
+        )
+        .collect();
+        assert_eq!(x.len(), 2);
+        assert!(x[0].is_err());
+        assert_eq!(x[1].as_ref().unwrap().id.as_deref(), Some("b"));
+
+        match Annotation::read(
+            "chr1\tx\texon\t1\t5\t.\t+\t.\tParent=nope\n".as_bytes(),
+            Format::GFF3,
+        ) {
+            Err(GffError::MissingParent { id: None, parent }) => assert_eq!(parent, "nope"),
+            y => panic!("expected a missing parent, got {:?}", y),
+        }
+    }
+
+    #[test]
+    fn splice_and_translate() {
+        let dc = DNACat::new();
+        let ribo = Ribosome::new(AminoCat::new(), RNACat::new());
+        let genome = dc.polymer_from_string(String::from(GENOME)).unwrap();
+
+        for (input, format, id) in [(GFF3, Format::GFF3, "tx1"), (GTF, Format::GTF, "t1")].iter() {
+            let a = Annotation::read(input.as_bytes(), *format).unwrap();
+            let tx = a.find(id).unwrap();
+
+            assert_eq!(
+                a.splice(tx, &genome, &dc).unwrap(),
+                dc.polymer_from_string(String::from("atgaaagtttaa"))
+                    .unwrap()
+            );
+            assert_eq!(
+                a.translate(tx, &genome, &dc, &ribo).unwrap(),
+                ribo.amino_c
+                    .polymer_from_string(String::from("metlysval"))
+                    .map(|mut x| {
+                        x.push(ribo.amino_c.morphisms.stop.clone());
+                        x
+                    })
+                    .unwrap()
+            );
+        }
+
+        let short = dc.polymer_from_string(String::from("CCCC")).unwrap();
+        let a = Annotation::read(GFF3.as_bytes(), Format::GFF3).unwrap();
+        assert_eq!(a.splice(a.find("tx1").unwrap(), &short, &dc), None);
+    }
+}
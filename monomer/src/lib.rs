@@ -1,11 +1,126 @@
+use std::error::Error;
+use std::fmt;
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum ParseError {
+    // Input at a 0-based offset that is not a letter of the alphabet.
+    Monomer {
+        found: String,
+        offset: usize,
+        alphabet: &'static str,
+    },
+    // Input whose length is not a whole number of width-letter monomers.
+    Length {
+        len: usize,
+        width: usize,
+    },
+    // Input that no reading of parses, with the error of each reading tried.
+    Readings(Vec<ParseError>),
+}
+
+impl ParseError {
+    pub fn monomer(found: String, alphabet: &'static str) -> Self {
+        ParseError::Monomer {
+            found,
+            offset: 0,
+            alphabet,
+        }
+    }
+
+    pub fn monomer_bytes(found: &[u8], alphabet: &'static str) -> Self {
+        Self::monomer(String::from_utf8_lossy(found).into_owned(), alphabet)
+    }
+
+    // Shifts the error along by n, for monomers parsed out of a larger input.
+    pub fn offset_by(self, n: usize) -> Self {
+        match self {
+            ParseError::Monomer {
+                found,
+                offset,
+                alphabet,
+            } => ParseError::Monomer {
+                found,
+                offset: offset + n,
+                alphabet,
+            },
+            ParseError::Readings(x) => {
+                ParseError::Readings(x.into_iter().map(|e| e.offset_by(n)).collect())
+            }
+            x => x,
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::Monomer {
+                found,
+                offset,
+                alphabet,
+            } => write!(
+                f,
+                "unexpected {:?} at offset {}, expected {}",
+                found, offset, alphabet
+            ),
+            ParseError::Length { len, width } => {
+                write!(f, "length {} is not a multiple of {}", len, width)
+            }
+            ParseError::Readings(x) => {
+                for (i, e) in x.iter().enumerate() {
+                    match i {
+                        0 => write!(f, "{}", e)?,
+                        _ => write!(f, "; or {}", e)?,
+                    }
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl Error for ParseError {}
+
 pub trait Mono: std::marker::Sized + PartialEq + Clone {
-    fn from_string(c: String) -> Option<Self>;
+    const ALPHABET: &'static str;
+    // Bytes per monomer when reading a polymer from raw input.
+    const WIDTH: usize = 1;
+
+    fn from_string(c: String) -> Result<Self, ParseError>;
+
+    fn maybe_from_string(c: String) -> Option<Self> {
+        Self::from_string(c).ok()
+    }
+
+    // NOTE: Falls back to from_string, alphabets should override this
+    // with a lookup that does not allocate.
+    fn from_bytes(b: &[u8]) -> Result<Self, ParseError> {
+        Self::from_string(String::from_utf8_lossy(b).into_owned())
+    }
+
+    fn from_byte(b: u8) -> Result<Self, ParseError> {
+        Self::from_bytes(&[b])
+    }
 }
 
 pub trait IMono: Mono {
     fn inverse(c: &Self) -> Self;
 }
 
+// NOTE: The single base checks are false for ambiguity codes, even
+// where is_g_or_c holds.
 pub trait NucleicAcid: IMono {
     fn is_g_or_c(&self) -> bool;
+    fn is_g(&self) -> bool;
+    fn is_c(&self) -> bool;
+    fn is_a(&self) -> bool;
+    // T in DNA, U in RNA.
+    fn is_t(&self) -> bool;
+}
+
+// NOTE: For alphabets of exactly four letters, used by packed polymers.
+// Codes follow UCSC .2bit order: T/U = 0, C = 1, A = 2, G = 3.
+pub trait Packed: NucleicAcid {
+    fn to_bits(&self) -> u8;
+    fn from_bits(b: u8) -> Self;
 }
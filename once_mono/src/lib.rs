@@ -1,4 +1,4 @@
-use monomer::{IMono, Mono, NucleicAcid};
+use monomer::{IMono, Mono, NucleicAcid, Packed, ParseError};
 use quartz::once_cell::{OnceCell, OnceVal};
 
 pub fn create<T>(t: T) -> OnceVal<T>
@@ -25,12 +25,15 @@ impl<T: Mono> Clone for Monomer<T> {
 }
 
 impl<T: Mono> Mono for Monomer<T> {
-    fn from_string(c: String) -> Option<Self> {
-        let x = T::from_string(c);
-        match x {
-            Some(y) => Some(Monomer(create(y))),
-            None => None,
-        }
+    const ALPHABET: &'static str = T::ALPHABET;
+    const WIDTH: usize = T::WIDTH;
+
+    fn from_string(c: String) -> Result<Self, ParseError> {
+        T::from_string(c).map(|y| Monomer(create(y)))
+    }
+
+    fn from_bytes(b: &[u8]) -> Result<Self, ParseError> {
+        T::from_bytes(b).map(|y| Monomer(create(y)))
     }
 }
 
@@ -56,12 +59,15 @@ impl<T: IMono> Clone for IMonomer<T> {
 }
 
 impl<T: IMono> Mono for IMonomer<T> {
-    fn from_string(s: String) -> Option<Self> {
-        let x = T::from_string(s);
-        match x {
-            Some(y) => Some(IMonomer(create(y))),
-            None => None,
-        }
+    const ALPHABET: &'static str = T::ALPHABET;
+    const WIDTH: usize = T::WIDTH;
+
+    fn from_string(s: String) -> Result<Self, ParseError> {
+        T::from_string(s).map(|y| IMonomer(create(y)))
+    }
+
+    fn from_bytes(b: &[u8]) -> Result<Self, ParseError> {
+        T::from_bytes(b).map(|y| IMonomer(create(y)))
     }
 }
 
@@ -75,6 +81,33 @@ impl<T: NucleicAcid> NucleicAcid for IMonomer<T> {
     fn is_g_or_c(&self) -> bool {
         self.0.read().as_ref().unwrap().is_g_or_c()
     }
+
+    fn is_g(&self) -> bool {
+        self.0.read().as_ref().unwrap().is_g()
+    }
+
+    fn is_c(&self) -> bool {
+        self.0.read().as_ref().unwrap().is_c()
+    }
+
+    fn is_a(&self) -> bool {
+        self.0.read().as_ref().unwrap().is_a()
+    }
+
+    fn is_t(&self) -> bool {
+        self.0.read().as_ref().unwrap().is_t()
+    }
+}
+
+// NOTE: from_bits allocates a fresh cell, use a Cat to share them instead.
+impl<T: Packed> Packed for IMonomer<T> {
+    fn to_bits(&self) -> u8 {
+        self.0.read().as_ref().unwrap().to_bits()
+    }
+
+    fn from_bits(b: u8) -> Self {
+        IMonomer(create(T::from_bits(b)))
+    }
 }
 
 impl<T: IMono> IMonomer<T> {
@@ -82,3 +115,32 @@ impl<T: IMono> IMonomer<T> {
         self.0.read()
     }
 }
+
+impl<T: Mono + std::fmt::Display> std::fmt::Display for Monomer<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.read().as_ref().unwrap().fmt(f)
+    }
+}
+
+impl<T: IMono + std::fmt::Display> std::fmt::Display for IMonomer<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.read().as_ref().unwrap().fmt(f)
+    }
+}
+
+// NOTE: Cells serialize as the monomer they hold. There is deliberately no
+// Deserialize, a fresh cell would not be shared with anything. Go through
+// the Cat instead, see category::MonomerSeed.
+#[cfg(feature = "serde")]
+impl<T: Mono + serde::Serialize> serde::Serialize for Monomer<T> {
+    fn serialize<S: serde::Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        self.0.read().as_ref().unwrap().serialize(s)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T: IMono + serde::Serialize> serde::Serialize for IMonomer<T> {
+    fn serialize<S: serde::Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        self.0.read().as_ref().unwrap().serialize(s)
+    }
+}
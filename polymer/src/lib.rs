@@ -1,6 +1,12 @@
-use monomer::{IMono, Mono, NucleicAcid};
+use monomer::{IMono, Mono, NucleicAcid, Packed, ParseError};
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+use std::iter::FromIterator;
+use std::marker::PhantomData;
+use std::ops::{Bound, RangeBounds};
 
-// TODO: See how to implement this thing as an iterator for read only
 pub trait Polymer<T>: std::marker::Sized + PartialEq
 where
     T: Mono,
@@ -8,28 +14,308 @@ where
     fn new() -> Self;
     fn push(&mut self, t: T);
     fn concat(&mut self, other: &mut Self);
+    fn len(&self) -> usize;
+    fn get(&self, i: usize) -> Option<T>;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn iter(&self) -> Iter<'_, T, Self> {
+        Iter {
+            polymer: self,
+            front: 0,
+            back: self.len(),
+            marker: PhantomData,
+        }
+    }
 
-    fn from_string(s: String) -> Option<Self> {
+    // NOTE: None if the range falls outside of the polymer.
+    fn subsequence<R: RangeBounds<usize>>(&self, r: R) -> Option<Self> {
+        let (start, end) = bounds(&r, self.len())?;
         let mut x = Self::new();
-        let mut ok = true;
-
-        for c in s.chars() {
-            match T::from_string(c.to_string()) {
-                Some(y) => x.push(y),
-                None => {
-                    ok = false;
-                    break;
-                }
+
+        for i in start..end {
+            x.push(self.get(i)?);
+        }
+
+        Some(x)
+    }
+
+    // NOTE: Panics if window or step is zero, like slice::windows.
+    fn profile(&self, p: Profile, window: usize, step: usize) -> Windows<'_, T, Self>
+    where
+        T: NucleicAcid,
+    {
+        assert!(window > 0 && step > 0, "window and step must be non-zero");
+        Windows {
+            polymer: self,
+            profile: p,
+            window,
+            step,
+            start: 0,
+            counts: Counts::default(),
+            counted: (0, 0),
+            cumulative: 0.0,
+            done: false,
+            marker: PhantomData,
+        }
+    }
+
+    fn from_string(s: String) -> Result<Self, ParseError> {
+        let mut x = Self::new();
+
+        for (i, c) in s.chars().enumerate() {
+            x.push(T::from_string(c.to_string()).map_err(|e| e.offset_by(i))?);
+        }
+
+        Ok(x)
+    }
+
+    fn maybe_from_string(s: String) -> Option<Self> {
+        Self::from_string(s).ok()
+    }
+
+    fn from_bytes(b: &[u8]) -> Result<Self, ParseError> {
+        if !b.len().is_multiple_of(T::WIDTH) {
+            return Err(ParseError::Length {
+                len: b.len(),
+                width: T::WIDTH,
+            });
+        }
+
+        let mut x = Self::new();
+
+        for (i, c) in b.chunks(T::WIDTH).enumerate() {
+            x.push(T::from_bytes(c).map_err(|e| e.offset_by(i * T::WIDTH))?);
+        }
+
+        Ok(x)
+    }
+}
+
+// Resolves a range against a polymer of length len into [start, end).
+pub fn bounds<R: RangeBounds<usize>>(r: &R, len: usize) -> Option<(usize, usize)> {
+    let start = match r.start_bound() {
+        Bound::Included(x) => *x,
+        Bound::Excluded(x) => x.checked_add(1)?,
+        Bound::Unbounded => 0,
+    };
+
+    let end = match r.end_bound() {
+        Bound::Included(x) => x.checked_add(1)?,
+        Bound::Excluded(x) => *x,
+        Bound::Unbounded => len,
+    };
+
+    match start <= end && end <= len {
+        true => Some((start, end)),
+        _ => None,
+    }
+}
+
+// NOTE: Yields owned monomers, so it works the same over packed and
+// unpacked polymers. For cells a clone is only a reference bump.
+pub struct Iter<'a, T, U>
+where
+    T: Mono,
+    U: Polymer<T>,
+{
+    polymer: &'a U,
+    front: usize,
+    back: usize,
+    marker: PhantomData<T>,
+}
+
+impl<'a, T: Mono, U: Polymer<T>> Iterator for Iter<'a, T, U> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        match self.front < self.back {
+            true => {
+                self.front += 1;
+                self.polymer.get(self.front - 1)
             }
+            _ => None,
         }
+    }
 
-        match ok {
-            true => Some(x),
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = self.back - self.front;
+        (n, Some(n))
+    }
+}
+
+impl<'a, T: Mono, U: Polymer<T>> DoubleEndedIterator for Iter<'a, T, U> {
+    fn next_back(&mut self) -> Option<T> {
+        match self.front < self.back {
+            true => {
+                self.back -= 1;
+                self.polymer.get(self.back)
+            }
+            _ => None,
+        }
+    }
+}
+
+impl<'a, T: Mono, U: Polymer<T>> ExactSizeIterator for Iter<'a, T, U> {}
+
+// NOTE: The owning counterpart of Iter, for polymers without a Vec to hand over.
+pub struct IntoIter<T, U>
+where
+    T: Mono,
+    U: Polymer<T>,
+{
+    polymer: U,
+    front: usize,
+    back: usize,
+    marker: PhantomData<T>,
+}
+
+impl<T: Mono, U: Polymer<T>> Iterator for IntoIter<T, U> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        match self.front < self.back {
+            true => {
+                self.front += 1;
+                self.polymer.get(self.front - 1)
+            }
+            _ => None,
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = self.back - self.front;
+        (n, Some(n))
+    }
+}
+
+impl<T: Mono, U: Polymer<T>> DoubleEndedIterator for IntoIter<T, U> {
+    fn next_back(&mut self) -> Option<T> {
+        match self.front < self.back {
+            true => {
+                self.back -= 1;
+                self.polymer.get(self.back)
+            }
             _ => None,
         }
     }
 }
 
+impl<T: Mono, U: Polymer<T>> ExactSizeIterator for IntoIter<T, U> {}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Profile {
+    // Fraction of the window that is G or C.
+    GC,
+    // (G - C) / (G + C)
+    GCSkew,
+    // (A - T) / (A + T)
+    ATSkew,
+    // Running total of GCSkew over the windows so far.
+    CumulativeGCSkew,
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+struct Counts {
+    gc: i64,
+    g: i64,
+    c: i64,
+    a: i64,
+    t: i64,
+}
+
+impl Counts {
+    fn add<T: NucleicAcid>(&mut self, x: &T, n: i64) {
+        self.gc += x.is_g_or_c() as i64 * n;
+        self.g += x.is_g() as i64 * n;
+        self.c += x.is_c() as i64 * n;
+        self.a += x.is_a() as i64 * n;
+        self.t += x.is_t() as i64 * n;
+    }
+}
+
+fn skew(x: i64, y: i64) -> f64 {
+    match x + y {
+        0 => 0.0,
+        d => (x - y) as f64 / d as f64,
+    }
+}
+
+// NOTE: Yields (start, end, value) for each window, the last one cut short
+// to end with the polymer. Counts are carried from window to window, so a
+// whole profile reads each monomer at most twice when step <= window.
+pub struct Windows<'a, T, U>
+where
+    T: NucleicAcid,
+    U: Polymer<T>,
+{
+    polymer: &'a U,
+    profile: Profile,
+    window: usize,
+    step: usize,
+    start: usize,
+    counts: Counts,
+    counted: (usize, usize),
+    cumulative: f64,
+    done: bool,
+    marker: PhantomData<T>,
+}
+
+impl<'a, T: NucleicAcid, U: Polymer<T>> Windows<'a, T, U> {
+    fn count(&mut self, start: usize, end: usize) {
+        let (lo, hi) = self.counted;
+
+        if start >= hi {
+            self.counts = Counts::default();
+            for i in start..end {
+                self.counts.add(&self.polymer.get(i).unwrap(), 1);
+            }
+        } else {
+            for i in lo..start {
+                self.counts.add(&self.polymer.get(i).unwrap(), -1);
+            }
+            for i in hi..end {
+                self.counts.add(&self.polymer.get(i).unwrap(), 1);
+            }
+        }
+
+        self.counted = (start, end);
+    }
+}
+
+impl<'a, T: NucleicAcid, U: Polymer<T>> Iterator for Windows<'a, T, U> {
+    type Item = (usize, usize, f64);
+
+    fn next(&mut self) -> Option<(usize, usize, f64)> {
+        let len = self.polymer.len();
+        if self.done || self.start >= len {
+            return None;
+        }
+
+        let start = self.start;
+        let end = (start + self.window).min(len);
+        self.count(start, end);
+
+        self.done = end == len;
+        self.start += self.step;
+
+        let c = self.counts;
+        let value = match self.profile {
+            Profile::GC => c.gc as f64 / (end - start) as f64,
+            Profile::GCSkew => skew(c.g, c.c),
+            Profile::ATSkew => skew(c.a, c.t),
+            Profile::CumulativeGCSkew => {
+                self.cumulative += skew(c.g, c.c);
+                self.cumulative
+            }
+        };
+
+        Some((start, end, value))
+    }
+}
+
 pub trait IPolymer<T>: Polymer<T>
 where
     T: IMono,
@@ -67,39 +353,59 @@ impl<T: Mono> Polymer<T> for Strand<T> {
     fn concat(&mut self, other: &mut Self) {
         self.contents.append(&mut other.contents);
     }
+
+    fn len(&self) -> usize {
+        self.contents.len()
+    }
+
+    fn get(&self, i: usize) -> Option<T> {
+        self.contents.get(i).cloned()
+    }
+
+    fn subsequence<R: RangeBounds<usize>>(&self, r: R) -> Option<Self> {
+        let (start, end) = bounds(&r, self.contents.len())?;
+        Some(Strand {
+            contents: self.contents[start..end].to_vec(),
+        })
+    }
 }
 
-impl<T: IMono> IPolymer<T> for Strand<T> {
-    fn inverse(&self) -> Self {
-        let mut next = Strand::new();
+impl<T: Mono> IntoIterator for Strand<T> {
+    type Item = T;
+    type IntoIter = std::vec::IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.contents.into_iter()
+    }
+}
 
-        for x in &self.contents {
-            // NOTE: Using clone here, because I'm counting on OnceVals
-            // worth remembering.
-            next.push(T::inverse(&x));
+impl<'a, T: Mono> IntoIterator for &'a Strand<T> {
+    type Item = &'a T;
+    type IntoIter = std::slice::Iter<'a, T>;
 
-            // TODO: TEST:
-            // next.push(T::inverse(&x))
+    fn into_iter(self) -> Self::IntoIter {
+        self.contents.iter()
+    }
+}
+
+impl<T: Mono> FromIterator<T> for Strand<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Strand {
+            contents: iter.into_iter().collect(),
         }
+    }
+}
 
-        next.contents = next.contents.into_iter().rev().collect();
-        next
+impl<T: IMono> IPolymer<T> for Strand<T> {
+    fn inverse(&self) -> Self {
+        self.contents.iter().rev().map(T::inverse).collect()
     }
 }
 
 impl<T: NucleicAcid> Strand<T> {
     pub fn gc_content(&self) -> (u64, u64) {
-        let mut n = 0;
-        let mut d = 0;
-
-        for x in &self.contents {
-            d = d + 1;
-            if x.is_g_or_c() {
-                n = n + 1;
-            }
-        }
-
-        (n, d)
+        let n = self.contents.iter().filter(|x| x.is_g_or_c()).count();
+        (n as u64, self.contents.len() as u64)
     }
 }
 
@@ -131,6 +437,46 @@ impl<T: IMono> Polymer<T> for Helix<T> {
     fn concat(&mut self, other: &mut Self) {
         self.strand.concat(&mut other.strand);
     }
+
+    fn len(&self) -> usize {
+        self.strand.len()
+    }
+
+    fn get(&self, i: usize) -> Option<T> {
+        self.strand.get(i)
+    }
+
+    fn subsequence<R: RangeBounds<usize>>(&self, r: R) -> Option<Self> {
+        Some(Helix {
+            strand: self.strand.subsequence(r)?,
+        })
+    }
+}
+
+impl<T: IMono> IntoIterator for Helix<T> {
+    type Item = T;
+    type IntoIter = std::vec::IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.strand.into_iter()
+    }
+}
+
+impl<'a, T: IMono> IntoIterator for &'a Helix<T> {
+    type Item = &'a T;
+    type IntoIter = std::slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.strand.contents.iter()
+    }
+}
+
+impl<T: IMono> FromIterator<T> for Helix<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Helix {
+            strand: iter.into_iter().collect(),
+        }
+    }
 }
 
 impl<T: IMono> IPolymer<T> for Helix<T> {
@@ -143,13 +489,11 @@ impl<T: IMono> IPolymer<T> for Helix<T> {
 
 impl<T: IMono> Helix<T> {
     pub fn pairs(&self) -> Vec<(T, T)> {
-        let mut next = Vec::<(T, T)>::new();
-        let a = &self.strand;
-        for x in &a.contents {
-            next.push((x.clone(), T::inverse(&x)));
-        }
-
-        next
+        self.strand
+            .contents
+            .iter()
+            .map(|x| (x.clone(), T::inverse(x)))
+            .collect()
     }
 
     pub fn strands(self) -> (Strand<T>, Strand<T>) {
@@ -164,3 +508,515 @@ impl<T: NucleicAcid> Helix<T> {
         self.strand.gc_content()
     }
 }
+
+// NOTE: A Strand with a Phred score per monomer, kept the same length as
+// the strand. Polymer::push has no score to give, so it scores 0.
+#[derive(Debug)]
+pub struct QualityStrand<T>
+where
+    T: Mono,
+{
+    strand: Strand<T>,
+    quality: Vec<u8>,
+}
+
+impl<T: Mono> PartialEq for QualityStrand<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.strand == other.strand && self.quality == other.quality
+    }
+}
+
+impl<T: Mono> Polymer<T> for QualityStrand<T> {
+    fn new() -> Self {
+        QualityStrand {
+            strand: Strand::new(),
+            quality: Vec::new(),
+        }
+    }
+
+    fn push(&mut self, t: T) {
+        self.push_scored(t, 0);
+    }
+
+    fn concat(&mut self, other: &mut Self) {
+        self.strand.concat(&mut other.strand);
+        self.quality.append(&mut other.quality);
+    }
+
+    fn len(&self) -> usize {
+        self.strand.len()
+    }
+
+    fn get(&self, i: usize) -> Option<T> {
+        self.strand.get(i)
+    }
+
+    fn subsequence<R: RangeBounds<usize>>(&self, r: R) -> Option<Self> {
+        let (start, end) = bounds(&r, self.len())?;
+        Some(QualityStrand {
+            strand: self.strand.subsequence(start..end)?,
+            quality: self.quality[start..end].to_vec(),
+        })
+    }
+}
+
+impl<T: IMono> IPolymer<T> for QualityStrand<T> {
+    fn inverse(&self) -> Self {
+        QualityStrand {
+            strand: self.strand.inverse(),
+            quality: self.quality.iter().rev().cloned().collect(),
+        }
+    }
+}
+
+impl<T: Mono> QualityStrand<T> {
+    // None unless there is exactly one score per monomer.
+    pub fn from_parts(strand: Strand<T>, quality: Vec<u8>) -> Option<Self> {
+        match strand.len() == quality.len() {
+            true => Some(QualityStrand { strand, quality }),
+            _ => None,
+        }
+    }
+
+    pub fn push_scored(&mut self, t: T, q: u8) {
+        self.strand.push(t);
+        self.quality.push(q);
+    }
+
+    pub fn get_scored(&self, i: usize) -> Option<(T, u8)> {
+        Some((self.strand.get(i)?, *self.quality.get(i)?))
+    }
+
+    pub fn strand(&self) -> &Strand<T> {
+        &self.strand
+    }
+
+    pub fn quality(&self) -> &[u8] {
+        &self.quality
+    }
+
+    pub fn into_parts(self) -> (Strand<T>, Vec<u8>) {
+        (self.strand, self.quality)
+    }
+}
+
+// NOTE: Stores four bases per byte, first base in the high bits.
+// Unused trailing bits of the last byte are always zero.
+#[derive(Debug)]
+pub struct PackedStrand<T>
+where
+    T: Packed,
+{
+    bytes: Vec<u8>,
+    len: usize,
+    marker: PhantomData<T>,
+}
+
+impl<T: Packed> PartialEq for PackedStrand<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && self.bytes == other.bytes
+    }
+}
+
+impl<T: Packed> Polymer<T> for PackedStrand<T> {
+    fn new() -> Self {
+        PackedStrand {
+            bytes: Vec::<u8>::new(),
+            len: 0,
+            marker: PhantomData,
+        }
+    }
+
+    fn push(&mut self, t: T) {
+        self.push_bits(t.to_bits());
+    }
+
+    fn concat(&mut self, other: &mut Self) {
+        match self.len % 4 {
+            0 => {
+                self.bytes.append(&mut other.bytes);
+                self.len += other.len;
+            }
+            _ => {
+                for i in 0..other.len {
+                    let b = other.bits(i);
+                    self.push_bits(b);
+                }
+                other.bytes.clear();
+            }
+        }
+        other.len = 0;
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn get(&self, i: usize) -> Option<T> {
+        match i < self.len {
+            true => Some(T::from_bits(self.bits(i))),
+            _ => None,
+        }
+    }
+
+    fn subsequence<R: RangeBounds<usize>>(&self, r: R) -> Option<Self> {
+        let (start, end) = bounds(&r, self.len)?;
+        let mut next = PackedStrand::<T>::new();
+
+        for i in start..end {
+            next.push_bits(self.bits(i));
+        }
+
+        Some(next)
+    }
+}
+
+impl<T: Packed> IntoIterator for PackedStrand<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T, Self>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter {
+            back: self.len,
+            polymer: self,
+            front: 0,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<'a, T: Packed> IntoIterator for &'a PackedStrand<T> {
+    type Item = T;
+    type IntoIter = Iter<'a, T, PackedStrand<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T: Packed> FromIterator<T> for PackedStrand<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut next = PackedStrand::<T>::new();
+        for x in iter {
+            next.push(x);
+        }
+        next
+    }
+}
+
+impl<T: Packed> IPolymer<T> for PackedStrand<T> {
+    fn inverse(&self) -> Self {
+        let table = Self::table(|b| T::inverse(&T::from_bits(b)).to_bits());
+        let mut next = PackedStrand::<T>::new();
+        next.bytes.reserve(self.bytes.len());
+
+        for i in (0..self.len).rev() {
+            next.push_bits(table[self.bits(i) as usize]);
+        }
+
+        next
+    }
+}
+
+impl<T: Packed> PackedStrand<T> {
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    pub fn gc_content(&self) -> (u64, u64) {
+        let table = Self::table(|b| T::from_bits(b).is_g_or_c() as u8);
+        let mut n = 0;
+
+        for i in 0..self.len {
+            n += table[self.bits(i) as usize] as u64;
+        }
+
+        (n, self.len as u64)
+    }
+
+    pub fn from_strand(s: &Strand<T>) -> Self {
+        let mut next = PackedStrand::<T>::new();
+        next.bytes.reserve(s.contents.len().div_ceil(4));
+
+        for x in &s.contents {
+            next.push_bits(x.to_bits());
+        }
+
+        next
+    }
+
+    pub fn to_strand(&self) -> Strand<T> {
+        let mut next = Strand::<T>::new();
+        next.contents.reserve(self.len);
+
+        for i in 0..self.len {
+            next.push(T::from_bits(self.bits(i)));
+        }
+
+        next
+    }
+
+    pub fn bits(&self, i: usize) -> u8 {
+        (self.bytes[i / 4] >> (6 - 2 * (i % 4))) & 0b11
+    }
+
+    pub fn push_bits(&mut self, b: u8) {
+        if self.len.is_multiple_of(4) {
+            self.bytes.push(0);
+        }
+
+        let last = self.bytes.len() - 1;
+        self.bytes[last] |= (b & 0b11) << (6 - 2 * (self.len % 4));
+        self.len += 1;
+    }
+
+    // NOTE: Evaluates f once per code, so cell-backed alphabets only
+    // allocate four monomers rather than one per base.
+    fn table<F: Fn(u8) -> u8>(f: F) -> [u8; 4] {
+        [f(0), f(1), f(2), f(3)]
+    }
+
+    pub fn from_helix(h: &Helix<T>) -> Self {
+        Self::from_strand(&h.strand)
+    }
+
+    pub fn to_helix(&self) -> Helix<T> {
+        Helix {
+            strand: self.to_strand(),
+        }
+    }
+}
+
+// NOTE: Monomers handed to each rayon task by the par_ methods, large
+// enough that splitting costs less than the work being split.
+#[cfg(feature = "parallel")]
+pub const PAR_CHUNK: usize = 1 << 14;
+
+#[cfg(feature = "parallel")]
+impl<T: IMono + Send + Sync> Strand<T> {
+    pub fn par_inverse(&self) -> Self {
+        let contents = self
+            .contents
+            .par_iter()
+            .with_min_len(PAR_CHUNK)
+            .rev()
+            .map(T::inverse)
+            .collect();
+
+        Strand { contents }
+    }
+}
+
+#[cfg(feature = "parallel")]
+impl<T: NucleicAcid + Send + Sync> Strand<T> {
+    pub fn par_gc_content(&self) -> (u64, u64) {
+        let n: usize = self
+            .contents
+            .par_chunks(PAR_CHUNK)
+            .map(|x| x.iter().filter(|y| y.is_g_or_c()).count())
+            .sum();
+
+        (n as u64, self.contents.len() as u64)
+    }
+}
+
+#[cfg(feature = "parallel")]
+impl<T: IMono + Send + Sync> Helix<T> {
+    pub fn par_inverse(&self) -> Self {
+        Helix {
+            strand: self.strand.par_inverse(),
+        }
+    }
+}
+
+#[cfg(feature = "parallel")]
+impl<T: NucleicAcid + Send + Sync> Helix<T> {
+    pub fn par_gc_content(&self) -> (u64, u64) {
+        self.strand.par_gc_content()
+    }
+}
+
+// NOTE: Both serialize as a plain sequence of monomers, so a Helix reads
+// back as a Strand and the other way round.
+#[cfg(feature = "serde")]
+impl<T: Mono + serde::Serialize> serde::Serialize for Strand<T> {
+    fn serialize<S: serde::Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        s.collect_seq(self.contents.iter())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T: IMono + serde::Serialize> serde::Serialize for Helix<T> {
+    fn serialize<S: serde::Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        self.strand.serialize(s)
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Side {
+    Top,
+    Bottom,
+}
+
+// NOTE: len unpaired columns on the 5' side of base at of one strand,
+// opposite a bulge in the other. Positions use the strand's own 5' to 3'
+// order, so they survive Duplex::inverse unchanged.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Gap {
+    pub side: Side,
+    pub at: usize,
+    pub len: usize,
+}
+
+// NOTE: Both strands of a double helix as they really are, rather than
+// one strand and its idealised inverse. Each strand is stored 5' to 3'.
+// Laid out in columns against the top strand, the bottom strand runs
+// 3' to 5' and its first column sits offset columns from the top's
+// first. A negative offset or a longer bottom strand gives an overhang.
+#[derive(Debug, PartialEq)]
+pub struct Duplex<T>
+where
+    T: IMono,
+{
+    pub top: Strand<T>,
+    pub bottom: Strand<T>,
+    pub offset: isize,
+    pub gaps: Vec<Gap>,
+}
+
+impl<T: IMono> Duplex<T> {
+    pub fn new(top: Strand<T>, bottom: Strand<T>, offset: isize) -> Self {
+        Duplex {
+            top,
+            bottom,
+            offset,
+            gaps: Vec::<Gap>::new(),
+        }
+    }
+
+    // The idealised duplex a Helix stands for.
+    pub fn from_helix(h: &Helix<T>) -> Self {
+        Duplex::new(h.strand.subsequence(..).unwrap(), h.strand.inverse(), 0)
+    }
+
+    pub fn with_gap(mut self, side: Side, at: usize, len: usize) -> Self {
+        self.gaps.push(Gap { side, at, len });
+        self
+    }
+
+    // Each strand in column order, with None for gap columns.
+    fn columns(&self, side: Side) -> Vec<Option<T>> {
+        let s = match side {
+            Side::Top => &self.top,
+            Side::Bottom => &self.bottom,
+        };
+
+        let mut next = Vec::<Option<T>>::new();
+        for i in 0..=s.len() {
+            for g in self.gaps.iter().filter(|g| g.side == side && g.at == i) {
+                next.extend((0..g.len).map(|_| None));
+            }
+            if let Some(x) = s.get(i) {
+                next.push(Some(x));
+            }
+        }
+
+        if side == Side::Bottom {
+            next.reverse();
+        }
+
+        next
+    }
+
+    // Every column across both strands, top first. None marks an
+    // overhang or a gap.
+    pub fn pairs(&self) -> Vec<(Option<T>, Option<T>)> {
+        let top = self.columns(Side::Top);
+        let bottom = self.columns(Side::Bottom);
+
+        let start = self.offset.min(0);
+        let end = (top.len() as isize).max(self.offset + bottom.len() as isize);
+
+        (start..end)
+            .map(|c| {
+                let t = match c >= 0 {
+                    true => top.get(c as usize).cloned().flatten(),
+                    _ => None,
+                };
+                let b = match c - self.offset >= 0 {
+                    true => bottom.get((c - self.offset) as usize).cloned().flatten(),
+                    _ => None,
+                };
+                (t, b)
+            })
+            .collect()
+    }
+
+    // Indices (into top, into bottom) of opposed bases that do not pair.
+    pub fn mismatches(&self) -> Vec<(usize, usize)> {
+        let mut t = 0;
+        let mut b = self.bottom.len();
+        let mut next = Vec::<(usize, usize)>::new();
+
+        for (x, y) in self.pairs() {
+            if y.is_some() {
+                b -= 1;
+            }
+            if let (Some(x), Some(y)) = (&x, &y) {
+                if &T::inverse(x) != y {
+                    next.push((t, b));
+                }
+            }
+            if x.is_some() {
+                t += 1;
+            }
+        }
+
+        next
+    }
+
+    pub fn is_perfect(&self) -> bool {
+        self.pairs()
+            .iter()
+            .all(|(x, y)| matches!((x, y), (Some(x), Some(y)) if &T::inverse(x) == y))
+    }
+
+    // Unpaired columns hanging off the (left, right) end of the duplex.
+    pub fn overhangs(&self) -> (usize, usize) {
+        let pairs = self.pairs();
+        let paired = |p: &&(Option<T>, Option<T>)| p.0.is_some() && p.1.is_some();
+
+        match pairs.iter().position(|p| paired(&p)) {
+            None => (pairs.len(), 0),
+            Some(l) => (l, pairs.iter().rev().position(|p| paired(&p)).unwrap()),
+        }
+    }
+
+    pub fn strands(self) -> (Strand<T>, Strand<T>) {
+        (self.top, self.bottom)
+    }
+
+    // The same duplex read from the other side.
+    pub fn inverse(&self) -> Self {
+        let top = self.columns(Side::Top).len() as isize;
+        let bottom = self.columns(Side::Bottom).len() as isize;
+
+        Duplex {
+            top: self.bottom.subsequence(..).unwrap(),
+            bottom: self.top.subsequence(..).unwrap(),
+            offset: self.offset + bottom - top,
+            gaps: self
+                .gaps
+                .iter()
+                .map(|g| Gap {
+                    side: match g.side {
+                        Side::Top => Side::Bottom,
+                        Side::Bottom => Side::Top,
+                    },
+                    at: g.at,
+                    len: g.len,
+                })
+                .collect(),
+        }
+    }
+}
@@ -0,0 +1,267 @@
+use amino::{Amino, AminoCell};
+use polymer::{Polymer, Strand};
+
+// EMBOSS's pKa for the free amino and carboxyl ends of the chain.
+pub const N_TERMINUS_PKA: f64 = 8.6;
+pub const C_TERMINUS_PKA: f64 = 3.6;
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Mass {
+    Average,
+    Monoisotopic,
+}
+
+// NOTE: Guruprasad et al. (1990) dipeptide instability weights, DIWV[x][y]
+// for x followed by y, rows and columns in DIWV_ORDER.
+const DIWV_ORDER: &[u8; 20] = b"ACEDGFIHKMLNQPSRTWVY";
+#[rustfmt::skip]
+const DIWV: [[f64; 20]; 20] = [
+    //   A       C       E       D       G       F       I       H       K       M       L       N       Q       P       S       R       T       W       V       Y
+    [  1.0,  44.94,   1.0,  -7.49,   1.0,    1.0,    1.0,  -7.49,   1.0,    1.0,    1.0,    1.0,    1.0,  20.26,   1.0,    1.0,    1.0,    1.0,    1.0,    1.0], // A
+    [  1.0,    1.0,   1.0,  20.26,   1.0,    1.0,    1.0,  33.60,   1.0,  33.60,  20.26,   1.0,  -6.54,  20.26,   1.0,    1.0,  33.60,  24.68,  -6.54,   1.0], // C
+    [  1.0,  44.94, 33.60,  20.26,   1.0,    1.0,  20.26,  -6.54,   1.0,    1.0,    1.0,    1.0,  20.26,  20.26,  20.26,   1.0,    1.0, -14.03,    1.0,    1.0], // E
+    [  1.0,    1.0,   1.0,    1.0,   1.0,  -6.54,    1.0,    1.0,  -7.49,   1.0,    1.0,    1.0,    1.0,    1.0,  20.26,  -6.54, -14.03,   1.0,    1.0,    1.0], // D
+    [-7.49,    1.0, -6.54,    1.0, 13.34,    1.0,  -7.49,    1.0,  -7.49,   1.0,    1.0,  -7.49,    1.0,    1.0,    1.0,    1.0,  -7.49,  13.34,    1.0,  -7.49], // G
+    [  1.0,    1.0,   1.0,  13.34,   1.0,    1.0,    1.0,    1.0, -14.03,   1.0,    1.0,    1.0,    1.0,  20.26,    1.0,    1.0,    1.0,    1.0,    1.0, 33.601], // F
+    [  1.0,    1.0, 44.94,    1.0,   1.0,    1.0,    1.0,  13.34,  -7.49,   1.0,  20.26,    1.0,    1.0,  -1.88,    1.0,    1.0,    1.0,    1.0,  -7.49,    1.0], // I
+    [  1.0,    1.0,   1.0,    1.0, -9.37,  -9.37,  44.94,    1.0,  24.68,   1.0,    1.0,  24.68,    1.0,  -1.88,    1.0,    1.0,  -6.54,  -1.88,    1.0,  44.94], // H
+    [  1.0,    1.0,   1.0,    1.0, -7.49,    1.0,  -7.49,    1.0,    1.0,  33.60,  -7.49,    1.0,  24.64,  -6.54,    1.0,  33.60,    1.0,    1.0,  -7.49,    1.0], // K
+    [13.34,    1.0,   1.0,    1.0,   1.0,    1.0,    1.0,  58.28,    1.0,  -1.88,    1.0,    1.0,  -6.54,  44.94,  44.94,  -6.54,  -1.88,    1.0,    1.0,  24.68], // M
+    [  1.0,    1.0,   1.0,    1.0,   1.0,    1.0,    1.0,    1.0,  -7.49,   1.0,    1.0,    1.0,  33.60,  20.26,    1.0,  20.26,    1.0,  24.68,    1.0,    1.0], // L
+    [  1.0,  -1.88,   1.0,    1.0,-14.03, -14.03,  44.94,    1.0,  24.68,   1.0,    1.0,    1.0,  -6.54,  -1.88,    1.0,    1.0,  -7.49,  -9.37,    1.0,    1.0], // N
+    [  1.0,  -6.54, 20.26,  20.26,   1.0,  -6.54,    1.0,    1.0,    1.0,   1.0,    1.0,    1.0,  20.26,  20.26,  44.94,    1.0,    1.0,    1.0,  -6.54,  -6.54], // Q
+    [20.26,  -6.54, 18.38,  -6.54,   1.0,  20.26,    1.0,    1.0,    1.0,  -6.54,    1.0,    1.0,  20.26,  20.26,  20.26,  -6.54,    1.0,  -1.88,  20.26,    1.0], // P
+    [  1.0,  33.60, 20.26,    1.0,   1.0,    1.0,    1.0,    1.0,    1.0,   1.0,    1.0,    1.0,  20.26,  44.94,  20.26,  20.26,    1.0,    1.0,    1.0,    1.0], // S
+    [  1.0,    1.0,   1.0,    1.0, -7.49,    1.0,    1.0,  20.26,    1.0,   1.0,    1.0,  13.34,  20.26,  20.26,  44.94,  58.28,    1.0,  58.28,    1.0,  -6.54], // R
+    [  1.0,    1.0, 20.26,    1.0, -7.49,  13.34,    1.0,    1.0,    1.0,   1.0,    1.0, -14.03,  -6.54,    1.0,    1.0,    1.0,    1.0, -14.03,    1.0,    1.0], // T
+    [-14.03,   1.0,   1.0,    1.0, -9.37,    1.0,    1.0,  24.68,    1.0,  24.68,  13.34,  13.34,    1.0,    1.0,    1.0,    1.0, -14.03,    1.0,  -7.49,    1.0], // W
+    [  1.0,    1.0,   1.0, -14.03, -7.49,    1.0,    1.0,    1.0,  -1.88,   1.0,    1.0,    1.0,    1.0,  20.26,    1.0,    1.0,  -7.49,    1.0,    1.0,  -6.54], // V
+    [24.68,    1.0, -6.54,  24.68, -7.49,    1.0,    1.0,  13.34,    1.0,  44.94,    1.0,    1.0,    1.0,  13.34,    1.0, -15.91,  -7.49,  -9.37,    1.0,  13.34], // Y
+];
+
+fn diwv(x: &Amino, y: &Amino) -> Option<f64> {
+    let i = |a: &Amino| {
+        let c = a.one_letter()? as u8;
+        DIWV_ORDER.iter().position(|b| *b == c)
+    };
+
+    Some(DIWV[i(x)?][i(y)?])
+}
+
+// NOTE: ProtParam style measures of a protein. START and STOP are not
+// residues, so they are dropped, which lets Ribosome output or a '*'
+// ended string go in as is. Measures that need a value for every residue
+// are None if the protein holds one without, such as an ambiguity code.
+#[derive(Debug, PartialEq, Clone)]
+pub struct ProteinAnalysis {
+    residues: Vec<Amino>,
+}
+
+impl ProteinAnalysis {
+    pub fn new(p: &Strand<AminoCell>) -> Self {
+        let residues = p
+            .iter()
+            .filter_map(|x| x.read().clone())
+            .filter(|x| *x != Amino::START && *x != Amino::STOP)
+            .collect();
+
+        ProteinAnalysis { residues }
+    }
+
+    pub fn residues(&self) -> &[Amino] {
+        &self.residues
+    }
+
+    pub fn count(&self, a: &Amino) -> usize {
+        self.residues.iter().filter(|x| *x == a).count()
+    }
+
+    // NOTE: (residue, count, percent of the protein) for each of the twenty
+    // standard residues, then any other residue the protein holds.
+    pub fn composition(&self) -> Vec<(Amino, usize, f64)> {
+        let mut all = Amino::STANDARD.to_vec();
+        for x in self.residues.iter() {
+            if !all.contains(x) {
+                all.push(x.clone());
+            }
+        }
+
+        let len = self.residues.len().max(1) as f64;
+        all.into_iter()
+            .map(|x| {
+                let n = self.count(&x);
+                (x, n, 100.0 * n as f64 / len)
+            })
+            .collect()
+    }
+
+    pub fn molecular_weight(&self, m: Mass) -> Option<f64> {
+        let (mass, water): (fn(&Amino) -> Option<f64>, f64) = match m {
+            Mass::Average => (Amino::average_mass, Amino::WATER_AVERAGE),
+            Mass::Monoisotopic => (Amino::monoisotopic_mass, Amino::WATER_MONOISOTOPIC),
+        };
+
+        self.residues
+            .iter()
+            .map(mass)
+            .sum::<Option<f64>>()
+            .map(|x| x + water)
+    }
+
+    // NOTE: Henderson-Hasselbalch over the ends and every ionizable side
+    // chain. Residues without a pKa, ambiguity codes too, carry no charge.
+    pub fn charge(&self, ph: f64) -> f64 {
+        let ion = |pka: f64, sign: i8| match sign > 0 {
+            true => 1.0 / (1.0 + 10f64.powf(ph - pka)),
+            _ => -1.0 / (1.0 + 10f64.powf(pka - ph)),
+        };
+
+        if self.residues.is_empty() {
+            return 0.0;
+        }
+
+        ion(N_TERMINUS_PKA, 1)
+            + ion(C_TERMINUS_PKA, -1)
+            + self
+                .residues
+                .iter()
+                .filter_map(|x| x.side_chain_pka())
+                .map(|(pka, sign)| ion(pka, sign))
+                .sum::<f64>()
+    }
+
+    // NOTE: The pH, found by bisection to within 0.001, where charge is
+    // zero. The pKa set is EMBOSS's rather than ProtParam's Bjellqvist
+    // set, so results can differ from ProtParam's in the first decimal.
+    // None for a protein with no residues, which has no termini to charge.
+    pub fn isoelectric_point(&self) -> Option<f64> {
+        if self.residues.is_empty() {
+            return None;
+        }
+
+        let (mut lo, mut hi) = (0.0, 14.0);
+        while hi - lo > 0.001 {
+            let mid = (lo + hi) / 2.0;
+            match self.charge(mid) > 0.0 {
+                true => lo = mid,
+                _ => hi = mid,
+            }
+        }
+
+        Some((lo + hi) / 2.0)
+    }
+
+    // NOTE: In M-1 cm-1 at 280nm, (every Cys paired in a cystine, every
+    // Cys reduced).
+    pub fn extinction_coefficient(&self) -> (u32, u32) {
+        let reduced: u32 = self.residues.iter().map(|x| x.extinction()).sum();
+        let cystines = (self.count(&Amino::Cys) / 2) as u32;
+
+        (reduced + cystines * Amino::CYSTINE_EXTINCTION, reduced)
+    }
+
+    // NOTE: Below 40 predicts a stable protein. None for fewer than two
+    // residues, as there are no dipeptides.
+    pub fn instability_index(&self) -> Option<f64> {
+        if self.residues.len() < 2 {
+            return None;
+        }
+
+        let x = self
+            .residues
+            .windows(2)
+            .map(|w| diwv(&w[0], &w[1]))
+            .sum::<Option<f64>>()?;
+
+        Some(10.0 * x / self.residues.len() as f64)
+    }
+
+    // Ikai (1980), from the mole percents of Ala, Val, Ile and Leu.
+    pub fn aliphatic_index(&self) -> f64 {
+        let percent = |a: &Amino| 100.0 * self.count(a) as f64 / self.residues.len().max(1) as f64;
+
+        percent(&Amino::Ala)
+            + 2.9 * percent(&Amino::Val)
+            + 3.9 * (percent(&Amino::Ile) + percent(&Amino::Leu))
+    }
+
+    // The grand average of hydropathy, Kyte-Doolittle over every residue.
+    pub fn gravy(&self) -> Option<f64> {
+        if self.residues.is_empty() {
+            return None;
+        }
+
+        let x = self
+            .residues
+            .iter()
+            .map(|x| x.hydropathy())
+            .sum::<Option<f64>>()?;
+
+        Some(x / self.residues.len() as f64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use amino::{AminoCat, Code};
+    use category::Cat;
+
+    fn analyse(s: &str) -> ProteinAnalysis {
+        let c = AminoCat::new();
+        ProteinAnalysis::new(&c.protein_from_string(String::from(s), Code::One).unwrap())
+    }
+
+    fn close(x: f64, y: f64, e: f64) {
+        assert!((x - y).abs() < e, "{} is not within {} of {}", x, e, y);
+    }
+
+    #[test]
+    fn weights_and_composition() {
+        let x = analyse("MKTW*");
+
+        assert_eq!(x.residues().len(), 4);
+        close(x.molecular_weight(Mass::Average).unwrap(), 564.70024, 1e-6);
+        close(
+            x.molecular_weight(Mass::Monoisotopic).unwrap(),
+            564.27300,
+            1e-6,
+        );
+
+        let c = x.composition();
+        assert_eq!(c.len(), 20);
+        assert_eq!(c[11], (Amino::Lys, 1, 25.0));
+        assert_eq!(c[0], (Amino::Ala, 0, 0.0));
+
+        let y = analyse("MKXU");
+        assert_eq!(y.molecular_weight(Mass::Average), None);
+        assert_eq!(y.composition().len(), 22);
+        assert_eq!(y.gravy(), None);
+        assert_eq!(y.instability_index(), None);
+    }
+
+    #[test]
+    fn charge_and_pi() {
+        let x = analyse("DEKRH");
+        close(x.charge(7.0), 0.21804, 1e-4);
+        close(x.isoelectric_point().unwrap(), 7.5507, 1e-3);
+
+        assert!(analyse("KKKK").isoelectric_point().unwrap() > 10.0);
+        assert!(analyse("DDDD").isoelectric_point().unwrap() < 4.0);
+        assert_eq!(analyse("").isoelectric_point(), None);
+        assert_eq!(analyse("*").isoelectric_point(), None);
+        assert!(x.charge(1.0) > 2.9);
+        assert!(x.charge(13.0) < -1.9);
+    }
+
+    #[test]
+    fn indices() {
+        let x = analyse("MAIVLCCWYG");
+
+        assert_eq!(x.extinction_coefficient(), (7115, 6990));
+        close(x.aliphatic_index(), 117.0, 1e-9);
+        close(x.gravy().unwrap(), 1.86, 1e-9);
+        close(x.instability_index().unwrap(), 28.04, 1e-9);
+        assert_eq!(analyse("M").instability_index(), None);
+    }
+}
@@ -1,16 +1,28 @@
 use amino::{Amino, AminoCat, AminoCell};
-use category::Cat;
+use category::Intern;
+use codon::GeneticCode;
 use polymer::{Polymer, Strand};
 use rna::{RNACat, RNACell, RNA};
+use std::ops::Range;
+
+#[cfg(feature = "parallel")]
+use polymer::PAR_CHUNK;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
 
 type RNACodon = (RNACell, RNACell, RNACell);
 
 pub struct Ribosome {
     pub amino_c: AminoCat,
     pub rna_c: RNACat,
+    pub code: GeneticCode,
+    // Whether the code's alternative starts (UUG, GUG, ...) open a protein
+    // as well as AUG.
+    pub alternative_starts: bool,
 }
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum Segment {
     Protein(Strand<AminoCell>),
     Junk(Strand<RNACell>),
@@ -18,17 +30,51 @@ pub enum Segment {
 
 impl Ribosome {
     pub fn new(a: AminoCat, r: RNACat) -> Self {
+        Self::with_code(a, r, GeneticCode::standard(), false)
+    }
+
+    // NOTE: code drives both directions: translation and the codon lists of
+    // amino_to_condon_vec. Only AUG opens a protein unless
+    // alternative_starts, when any of the code's start codons does.
+    pub fn with_code(a: AminoCat, r: RNACat, code: GeneticCode, alternative_starts: bool) -> Self {
         Ribosome {
             amino_c: a,
             rna_c: r,
+            code,
+            alternative_starts,
         }
     }
 
+    // NOTE: Reads back what serializing translate's output wrote, with the
+    // cells taken from this Ribosome's Cats.
+    #[cfg(feature = "serde")]
+    pub fn deserialize_segments<'de, D>(&self, d: D) -> Result<Vec<Segment>, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        #[derive(serde::Deserialize)]
+        #[serde(rename = "Segment")]
+        enum Plain {
+            Protein(Vec<Amino>),
+            Junk(Vec<RNA>),
+        }
+
+        let x = <Vec<Plain> as serde::Deserialize>::deserialize(d)?;
+        Ok(x.into_iter()
+            .map(|y| match y {
+                Plain::Protein(z) => {
+                    Segment::Protein(z.iter().map(|a| self.amino_c.intern(a)).collect())
+                }
+                Plain::Junk(z) => Segment::Junk(z.iter().map(|r| self.rna_c.intern(r)).collect()),
+            })
+            .collect())
+    }
+
     pub fn plain_translate(&self, r: Strand<RNACell>) -> Option<Strand<AminoCell>> {
-        match r.contents.len() % 3 == 0 {
+        match r.len() % 3 == 0 {
             false => None,
             _ => {
-                let mut x = r.contents.into_iter().peekable();
+                let mut x = r.into_iter().peekable();
                 let mut y = Strand::<AminoCell>::new();
 
                 while x.peek().is_some() {
@@ -43,49 +89,71 @@ impl Ribosome {
         }
     }
 
+    // NOTE: Same result as plain_translate. Chunks are a whole number of
+    // codons long, so no codon is split between two tasks.
+    #[cfg(feature = "parallel")]
+    pub fn par_plain_translate(&self, r: Strand<RNACell>) -> Option<Strand<AminoCell>> {
+        match r.len() % 3 == 0 {
+            false => None,
+            _ => {
+                let contents = r
+                    .contents
+                    .par_chunks(3 * PAR_CHUNK)
+                    .flat_map_iter(|x| {
+                        x.chunks(3).map(|z| {
+                            self.codon_to_amino(&(z[0].clone(), z[1].clone(), z[2].clone()))
+                        })
+                    })
+                    .collect();
+
+                Some(Strand { contents })
+            }
+        }
+    }
+
     pub fn translate(&self, r: Strand<RNACell>) -> Option<Vec<Segment>> {
+        Some(
+            self.translate_spans(r)?
+                .into_iter()
+                .map(|(x, _)| x)
+                .collect(),
+        )
+    }
+
+    // NOTE: As translate, with the 0-based, half open range of r each
+    // segment came from. A protein's range runs from its start codon to the
+    // end of its stop codon, though neither is in the protein itself.
+    pub fn translate_spans(&self, r: Strand<RNACell>) -> Option<Vec<(Segment, Range<usize>)>> {
         let mut is_junk = true;
-        let mut segments = Vec::<Segment>::new();
+        let mut segments = Vec::<(Segment, Range<usize>)>::new();
+        let mut from = 0;
 
         let mut current_protien = Strand::<AminoCell>::new();
         let mut current_rna = Strand::<RNACell>::new();
         let mut current_codon = Strand::<RNACell>::new();
 
-        for nucl in r.contents {
+        for (i, nucl) in r.into_iter().enumerate() {
             match is_junk {
                 true => {
                     current_rna.push(nucl.clone());
                     current_codon.push(nucl.clone());
 
-                    if current_codon.contents.len() == 3 {
-                        let x = self.codon_to_amino(&(
-                            current_codon.contents[0].clone(),
-                            current_codon.contents[1].clone(),
-                            current_codon.contents[2].clone(),
-                        ));
-
-                        if x == self.amino_c.morphisms.met {
+                    if current_codon.len() == 3 {
+                        if self.is_start(&Self::codon_of(&current_codon)) {
                             is_junk = false;
-                            current_rna
-                                .contents
-                                .truncate(current_rna.contents.len() - 3);
+                            current_rna = current_rna.subsequence(..current_rna.len() - 3)?;
 
-                            if current_rna.contents.len() > 0 {
-                                segments.push(Segment::Junk(current_rna));
+                            if !current_rna.is_empty() {
+                                segments.push((Segment::Junk(current_rna), from..i - 2));
                             }
+                            from = i - 2;
 
                             current_codon = Strand::<RNACell>::new();
                             current_rna = Strand::<RNACell>::new();
                         } else {
                             // TODO: GET FANCIER.
 
-                            let fst = current_codon.contents[1].clone();
-                            let snd = current_codon.contents[2].clone();
-
-                            current_codon = Strand::<RNACell>::new();
-
-                            current_codon.push(fst);
-                            current_codon.push(snd);
+                            current_codon = current_codon.subsequence(1..)?;
                         }
                     }
                 }
@@ -93,18 +161,15 @@ impl Ribosome {
                 _ => {
                     current_codon.push(nucl.clone());
 
-                    if current_codon.contents.len() == 3 {
-                        let x = self.codon_to_amino(&(
-                            current_codon.contents[0].clone(),
-                            current_codon.contents[1].clone(),
-                            current_codon.contents[2].clone(),
-                        ));
+                    if current_codon.len() == 3 {
+                        let x = self.codon_to_amino(&Self::codon_of(&current_codon));
 
                         if x == self.amino_c.morphisms.stop {
-                            if current_protien.contents.len() > 0 {
-                                segments.push(Segment::Protein(current_protien));
+                            if !current_protien.is_empty() {
+                                segments.push((Segment::Protein(current_protien), from..i + 1));
                                 current_protien = Strand::<AminoCell>::new();
                             }
+                            from = i + 1;
 
                             is_junk = true;
                         } else {
@@ -119,6 +184,25 @@ impl Ribosome {
         Some(segments)
     }
 
+    fn codon_of(s: &Strand<RNACell>) -> RNACodon {
+        (s.get(0).unwrap(), s.get(1).unwrap(), s.get(2).unwrap())
+    }
+
+    pub fn is_start(&self, c: &RNACodon) -> bool {
+        match self.alternative_starts {
+            true => self.code.is_start(c),
+            _ => c == &self.aug(),
+        }
+    }
+
+    fn aug(&self) -> RNACodon {
+        (
+            self.rna_c.a.clone(),
+            self.rna_c.u.clone(),
+            self.rna_c.g.clone(),
+        )
+    }
+
     pub fn codon_eq(&self, fst: &RNACodon, snd: &RNACodon) -> bool {
         self.codon_to_amino(fst) == self.codon_to_amino(snd)
     }
@@ -133,8 +217,8 @@ impl Ribosome {
         let mut result: Vec<Vec<RNACell>> = Vec::new();
         let mut first = true;
 
-        for a in strand.contents.iter() {
-            let inner_vec = self.amino_to_condon_vec(a)?;
+        for a in strand.iter() {
+            let inner_vec = self.amino_to_condon_vec(&a)?;
 
             if first {
                 first = false;
@@ -165,518 +249,54 @@ impl Ribosome {
 
     pub fn amino_to_condon_vec(&self, a: &AminoCell) -> Option<Vec<RNACodon>> {
         let x = a.read();
-        match x.as_ref() {
-            None => None,
-            Some(y) => match y {
-                &Amino::Ala => Some(vec![
-                    (
-                        self.rna_c.g.clone(),
-                        self.rna_c.c.clone(),
-                        self.rna_c.u.clone(),
-                    ),
-                    (
-                        self.rna_c.g.clone(),
-                        self.rna_c.c.clone(),
-                        self.rna_c.c.clone(),
-                    ),
-                    (
-                        self.rna_c.g.clone(),
-                        self.rna_c.c.clone(),
-                        self.rna_c.a.clone(),
-                    ),
-                    (
-                        self.rna_c.g.clone(),
-                        self.rna_c.c.clone(),
-                        self.rna_c.g.clone(),
-                    ),
-                ]),
-
-                &Amino::Arg => Some(vec![
-                    (
-                        self.rna_c.c.clone(),
-                        self.rna_c.g.clone(),
-                        self.rna_c.u.clone(),
-                    ),
-                    (
-                        self.rna_c.c.clone(),
-                        self.rna_c.g.clone(),
-                        self.rna_c.c.clone(),
-                    ),
-                    (
-                        self.rna_c.c.clone(),
-                        self.rna_c.g.clone(),
-                        self.rna_c.a.clone(),
-                    ),
-                    (
-                        self.rna_c.c.clone(),
-                        self.rna_c.g.clone(),
-                        self.rna_c.g.clone(),
-                    ),
-                ]),
-
-                &Amino::Asn => Some(vec![
-                    (
-                        self.rna_c.a.clone(),
-                        self.rna_c.a.clone(),
-                        self.rna_c.u.clone(),
-                    ),
-                    (
-                        self.rna_c.a.clone(),
-                        self.rna_c.a.clone(),
-                        self.rna_c.c.clone(),
-                    ),
-                ]),
-
-                &Amino::Asp => Some(vec![
-                    (
-                        self.rna_c.g.clone(),
-                        self.rna_c.a.clone(),
-                        self.rna_c.u.clone(),
-                    ),
-                    (
-                        self.rna_c.g.clone(),
-                        self.rna_c.a.clone(),
-                        self.rna_c.c.clone(),
-                    ),
-                ]),
-
-                &Amino::Cys => Some(vec![
-                    (
-                        self.rna_c.u.clone(),
-                        self.rna_c.g.clone(),
-                        self.rna_c.u.clone(),
-                    ),
-                    (
-                        self.rna_c.u.clone(),
-                        self.rna_c.g.clone(),
-                        self.rna_c.c.clone(),
-                    ),
-                ]),
-
-                &Amino::Gln => Some(vec![
-                    (
-                        self.rna_c.c.clone(),
-                        self.rna_c.a.clone(),
-                        self.rna_c.a.clone(),
-                    ),
-                    (
-                        self.rna_c.c.clone(),
-                        self.rna_c.a.clone(),
-                        self.rna_c.g.clone(),
-                    ),
-                ]),
-
-                &Amino::Glu => Some(vec![
-                    (
-                        self.rna_c.g.clone(),
-                        self.rna_c.a.clone(),
-                        self.rna_c.a.clone(),
-                    ),
-                    (
-                        self.rna_c.g.clone(),
-                        self.rna_c.a.clone(),
-                        self.rna_c.g.clone(),
-                    ),
-                ]),
-
-                &Amino::Gly => Some(vec![
-                    (
-                        self.rna_c.g.clone(),
-                        self.rna_c.g.clone(),
-                        self.rna_c.u.clone(),
-                    ),
-                    (
-                        self.rna_c.g.clone(),
-                        self.rna_c.g.clone(),
-                        self.rna_c.c.clone(),
-                    ),
-                    (
-                        self.rna_c.g.clone(),
-                        self.rna_c.g.clone(),
-                        self.rna_c.a.clone(),
-                    ),
-                    (
-                        self.rna_c.g.clone(),
-                        self.rna_c.g.clone(),
-                        self.rna_c.g.clone(),
-                    ),
-                ]),
-
-                &Amino::His => Some(vec![
-                    (
-                        self.rna_c.c.clone(),
-                        self.rna_c.a.clone(),
-                        self.rna_c.u.clone(),
-                    ),
-                    (
-                        self.rna_c.c.clone(),
-                        self.rna_c.a.clone(),
-                        self.rna_c.c.clone(),
-                    ),
-                ]),
-
-                &Amino::Ile => Some(vec![
-                    (
-                        self.rna_c.a.clone(),
-                        self.rna_c.u.clone(),
-                        self.rna_c.u.clone(),
-                    ),
-                    (
-                        self.rna_c.a.clone(),
-                        self.rna_c.u.clone(),
-                        self.rna_c.c.clone(),
-                    ),
-                    (
-                        self.rna_c.a.clone(),
-                        self.rna_c.u.clone(),
-                        self.rna_c.a.clone(),
-                    ),
-                ]),
-
-                &Amino::Leu => Some(vec![
-                    (
-                        self.rna_c.u.clone(),
-                        self.rna_c.u.clone(),
-                        self.rna_c.a.clone(),
-                    ),
-                    (
-                        self.rna_c.u.clone(),
-                        self.rna_c.u.clone(),
-                        self.rna_c.g.clone(),
-                    ),
-                    (
-                        self.rna_c.c.clone(),
-                        self.rna_c.u.clone(),
-                        self.rna_c.u.clone(),
-                    ),
-                    (
-                        self.rna_c.c.clone(),
-                        self.rna_c.u.clone(),
-                        self.rna_c.c.clone(),
-                    ),
-                    (
-                        self.rna_c.c.clone(),
-                        self.rna_c.u.clone(),
-                        self.rna_c.a.clone(),
-                    ),
-                    (
-                        self.rna_c.c.clone(),
-                        self.rna_c.u.clone(),
-                        self.rna_c.g.clone(),
-                    ),
-                ]),
-
-                &Amino::Lys => Some(vec![
-                    (
-                        self.rna_c.a.clone(),
-                        self.rna_c.a.clone(),
-                        self.rna_c.a.clone(),
-                    ),
-                    (
-                        self.rna_c.a.clone(),
-                        self.rna_c.a.clone(),
-                        self.rna_c.g.clone(),
-                    ),
-                ]),
-
-                &Amino::Met => Some(vec![(
-                    self.rna_c.a.clone(),
-                    self.rna_c.u.clone(),
-                    self.rna_c.g.clone(),
-                )]),
-
-                &Amino::Phe => Some(vec![
-                    (
-                        self.rna_c.u.clone(),
-                        self.rna_c.u.clone(),
-                        self.rna_c.u.clone(),
-                    ),
-                    (
-                        self.rna_c.u.clone(),
-                        self.rna_c.u.clone(),
-                        self.rna_c.c.clone(),
-                    ),
-                ]),
-
-                &Amino::Pro => Some(vec![
-                    (
-                        self.rna_c.c.clone(),
-                        self.rna_c.c.clone(),
-                        self.rna_c.u.clone(),
-                    ),
-                    (
-                        self.rna_c.c.clone(),
-                        self.rna_c.c.clone(),
-                        self.rna_c.c.clone(),
-                    ),
-                    (
-                        self.rna_c.c.clone(),
-                        self.rna_c.c.clone(),
-                        self.rna_c.a.clone(),
-                    ),
-                    (
-                        self.rna_c.c.clone(),
-                        self.rna_c.c.clone(),
-                        self.rna_c.g.clone(),
-                    ),
-                ]),
-
-                &Amino::Ser => Some(vec![
-                    (
-                        self.rna_c.u.clone(),
-                        self.rna_c.c.clone(),
-                        self.rna_c.u.clone(),
-                    ),
-                    (
-                        self.rna_c.u.clone(),
-                        self.rna_c.c.clone(),
-                        self.rna_c.c.clone(),
-                    ),
-                    (
-                        self.rna_c.u.clone(),
-                        self.rna_c.c.clone(),
-                        self.rna_c.a.clone(),
-                    ),
-                    (
-                        self.rna_c.u.clone(),
-                        self.rna_c.c.clone(),
-                        self.rna_c.g.clone(),
-                    ),
-                    (
-                        self.rna_c.a.clone(),
-                        self.rna_c.g.clone(),
-                        self.rna_c.u.clone(),
-                    ),
-                    (
-                        self.rna_c.a.clone(),
-                        self.rna_c.g.clone(),
-                        self.rna_c.c.clone(),
-                    ),
-                ]),
-
-                &Amino::Thr => Some(vec![
-                    (
-                        self.rna_c.a.clone(),
-                        self.rna_c.c.clone(),
-                        self.rna_c.u.clone(),
-                    ),
-                    (
-                        self.rna_c.a.clone(),
-                        self.rna_c.c.clone(),
-                        self.rna_c.c.clone(),
-                    ),
-                    (
-                        self.rna_c.a.clone(),
-                        self.rna_c.c.clone(),
-                        self.rna_c.a.clone(),
-                    ),
-                    (
-                        self.rna_c.a.clone(),
-                        self.rna_c.c.clone(),
-                        self.rna_c.g.clone(),
-                    ),
-                ]),
-
-                &Amino::Trp => Some(vec![(
-                    self.rna_c.u.clone(),
-                    self.rna_c.g.clone(),
-                    self.rna_c.g.clone(),
-                )]),
-
-                &Amino::Tyr => Some(vec![
-                    (
-                        self.rna_c.u.clone(),
-                        self.rna_c.a.clone(),
-                        self.rna_c.u.clone(),
-                    ),
-                    (
-                        self.rna_c.u.clone(),
-                        self.rna_c.a.clone(),
-                        self.rna_c.c.clone(),
-                    ),
-                ]),
-
-                &Amino::Val => Some(vec![
-                    (
-                        self.rna_c.g.clone(),
-                        self.rna_c.u.clone(),
-                        self.rna_c.u.clone(),
-                    ),
-                    (
-                        self.rna_c.g.clone(),
-                        self.rna_c.u.clone(),
-                        self.rna_c.c.clone(),
-                    ),
-                    (
-                        self.rna_c.g.clone(),
-                        self.rna_c.u.clone(),
-                        self.rna_c.a.clone(),
-                    ),
-                    (
-                        self.rna_c.g.clone(),
-                        self.rna_c.u.clone(),
-                        self.rna_c.g.clone(),
-                    ),
-                ]),
-
-                &Amino::START => Some(vec![(
-                    self.rna_c.a.clone(),
-                    self.rna_c.u.clone(),
-                    self.rna_c.g.clone(),
-                )]),
-
-                &Amino::STOP => Some(vec![
-                    (
-                        self.rna_c.u.clone(),
-                        self.rna_c.a.clone(),
-                        self.rna_c.a.clone(),
-                    ),
-                    (
-                        self.rna_c.u.clone(),
-                        self.rna_c.a.clone(),
-                        self.rna_c.g.clone(),
-                    ),
-                    (
-                        self.rna_c.u.clone(),
-                        self.rna_c.g.clone(),
-                        self.rna_c.a.clone(),
-                    ),
-                ]),
+        let y = x.as_ref()?;
+        let plain = match y {
+            Amino::START => match self.alternative_starts {
+                true => self.code.starts(),
+                _ => return Some(vec![self.aug()]),
             },
-        }
-    }
-
-    pub fn codon_to_amino(&self, r: &RNACodon) -> AminoCell {
-        let (fst, snd, thd) = r;
 
-        let x = fst.read();
-        let y = snd.read();
-        let z = thd.read();
+            // NOTE: Recoded by context rather than by table.
+            Amino::Sec => vec![(RNA::U, RNA::G, RNA::A)],
+            Amino::Pyl => vec![(RNA::U, RNA::A, RNA::G)],
 
-        let plain_codon = (
-            x.as_ref().unwrap(),
-            y.as_ref().unwrap(),
-            z.as_ref().unwrap(),
-        );
+            // NOTE: An ambiguity code back-translates to every codon of
+            // every residue it stands for.
+            Amino::Asx | Amino::Glx | Amino::Xle | Amino::Xaa => y
+                .members()
+                .iter()
+                .flat_map(|m| self.code.codons(m))
+                .collect(),
 
-        match plain_codon {
-            // Reference: https://cnx.org/contents/GFy_h8cu@9.87:QEibhJMi@8/The-Genetic-Code
-            // Square 1:
-            (&RNA::U, &RNA::U, &RNA::A) | (&RNA::U, &RNA::U, &RNA::G) => {
-                self.amino_c.morphisms.leu.clone()
-            }
-            (&RNA::U, &RNA::U, &RNA::U) | (&RNA::U, &RNA::U, &RNA::C) => {
-                self.amino_c.morphisms.phe.clone()
-            }
-
-            // Square 2:
-            (&RNA::U, &RNA::C, &RNA::U)
-            | (&RNA::U, &RNA::C, &RNA::C)
-            | (&RNA::U, &RNA::C, &RNA::A)
-            | (&RNA::U, &RNA::C, &RNA::G) => self.amino_c.morphisms.ser.clone(),
-
-            // Square 3:
-            (&RNA::U, &RNA::A, &RNA::U) | (&RNA::U, &RNA::A, &RNA::C) => {
-                self.amino_c.morphisms.tyr.clone()
-            }
-            (&RNA::U, &RNA::A, &RNA::A) | (&RNA::U, &RNA::A, &RNA::G) => {
-                self.amino_c.morphisms.stop.clone()
-            }
-
-            // Square 4:
-            (&RNA::U, &RNA::G, &RNA::U) | (&RNA::U, &RNA::G, &RNA::C) => {
-                self.amino_c.morphisms.cys.clone()
-            }
-            (&RNA::U, &RNA::G, &RNA::A) => self.amino_c.morphisms.stop.clone(),
-            (&RNA::U, &RNA::G, &RNA::G) => self.amino_c.morphisms.trp.clone(),
-
-            // Square 5:
-            (&RNA::C, &RNA::U, &RNA::U)
-            | (&RNA::C, &RNA::U, &RNA::C)
-            | (&RNA::C, &RNA::U, &RNA::A)
-            | (&RNA::C, &RNA::U, &RNA::G) => self.amino_c.morphisms.leu.clone(),
-
-            // Square 6:
-            (&RNA::C, &RNA::C, &RNA::U)
-            | (&RNA::C, &RNA::C, &RNA::C)
-            | (&RNA::C, &RNA::C, &RNA::A)
-            | (&RNA::C, &RNA::C, &RNA::G) => self.amino_c.morphisms.pro.clone(),
-
-            // Square 7:
-            (&RNA::C, &RNA::A, &RNA::U) | (&RNA::C, &RNA::A, &RNA::C) => {
-                self.amino_c.morphisms.his.clone()
-            }
-            (&RNA::C, &RNA::A, &RNA::A) | (&RNA::C, &RNA::A, &RNA::G) => {
-                self.amino_c.morphisms.gln.clone()
-            }
-
-            // Square 8:
-            (&RNA::C, &RNA::G, &RNA::U)
-            | (&RNA::C, &RNA::G, &RNA::C)
-            | (&RNA::C, &RNA::G, &RNA::A)
-            | (&RNA::C, &RNA::G, &RNA::G) => self.amino_c.morphisms.arg.clone(),
-
-            // Square 9:
-            (&RNA::A, &RNA::U, &RNA::U)
-            | (&RNA::A, &RNA::U, &RNA::C)
-            | (&RNA::A, &RNA::U, &RNA::A) => self.amino_c.morphisms.ile.clone(),
-            // NOTE: Not start, assumed start has been dealt with
-            (&RNA::A, &RNA::U, &RNA::G) => self.amino_c.morphisms.met.clone(),
-
-            // Square 10:
-            (&RNA::A, &RNA::C, &RNA::U)
-            | (&RNA::A, &RNA::C, &RNA::C)
-            | (&RNA::A, &RNA::C, &RNA::A)
-            | (&RNA::A, &RNA::C, &RNA::G) => self.amino_c.morphisms.thr.clone(),
-
-            // Square 11:
-            (&RNA::A, &RNA::A, &RNA::U) | (&RNA::A, &RNA::A, &RNA::C) => {
-                self.amino_c.morphisms.asn.clone()
-            }
-            (&RNA::A, &RNA::A, &RNA::A) | (&RNA::A, &RNA::A, &RNA::G) => {
-                self.amino_c.morphisms.lys.clone()
-            }
-
-            // Square 12:
-            (&RNA::A, &RNA::G, &RNA::U) | (&RNA::A, &RNA::G, &RNA::C) => {
-                self.amino_c.morphisms.ser.clone()
-            }
-            (&RNA::A, &RNA::G, &RNA::A) | (&RNA::A, &RNA::G, &RNA::G) => {
-                self.amino_c.morphisms.arg.clone()
-            }
-
-            // Square 13:
-            (&RNA::G, &RNA::U, &RNA::U)
-            | (&RNA::G, &RNA::U, &RNA::C)
-            | (&RNA::G, &RNA::U, &RNA::A)
-            | (&RNA::G, &RNA::U, &RNA::G) => self.amino_c.morphisms.val.clone(),
-
-            // Square 14:
-            (&RNA::G, &RNA::C, &RNA::U)
-            | (&RNA::G, &RNA::C, &RNA::C)
-            | (&RNA::G, &RNA::C, &RNA::A)
-            | (&RNA::G, &RNA::C, &RNA::G) => self.amino_c.morphisms.ala.clone(),
-
-            // Square 15:
-            (&RNA::G, &RNA::A, &RNA::U) | (&RNA::G, &RNA::A, &RNA::C) => {
-                self.amino_c.morphisms.asp.clone()
-            }
-            (&RNA::G, &RNA::A, &RNA::A) | (&RNA::G, &RNA::A, &RNA::G) => {
-                self.amino_c.morphisms.glu.clone()
-            }
+            _ => self.code.codons(y),
+        };
 
-            // Square 16:
-            (&RNA::G, &RNA::G, &RNA::U)
-            | (&RNA::G, &RNA::G, &RNA::C)
-            | (&RNA::G, &RNA::G, &RNA::A)
-            | (&RNA::G, &RNA::G, &RNA::G) => self.amino_c.morphisms.gly.clone(),
+        match plain.is_empty() {
+            true => None,
+            _ => Some(
+                plain
+                    .iter()
+                    .map(|(f, s, t)| {
+                        (
+                            self.rna_c.intern(f),
+                            self.rna_c.intern(s),
+                            self.rna_c.intern(t),
+                        )
+                    })
+                    .collect(),
+            ),
         }
     }
+
+    pub fn codon_to_amino(&self, r: &RNACodon) -> AminoCell {
+        self.amino_c.intern(&self.code.translate(r))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use category::Cat;
 
     #[test]
     fn plain_translate_test() {
@@ -817,6 +437,113 @@ mod tests {
         }
     }
 
+    #[test]
+    fn translate_spans_test() {
+        let ac = AminoCat::new();
+        let rc = RNACat::new();
+        let ribo = Ribosome::new(ac, rc);
+
+        let strand = ribo
+            .rna_c
+            .polymer_from_string(String::from("ccaugcaguaaaugaaauagg"))
+            .unwrap();
+        let spans = ribo.translate_spans(strand).unwrap();
+
+        let ranges: Vec<Range<usize>> = spans.iter().map(|(_, r)| r.clone()).collect();
+        assert_eq!(ranges, vec![0..2, 2..11, 11..20]);
+
+        match &spans[2].0 {
+            Segment::Protein(x) => assert_eq!(x.contents, vec![ribo.amino_c.morphisms.lys.clone()]),
+            _ => panic!("Expected protein, got Junk in translate_spans_test!"),
+        }
+    }
+
+    #[test]
+    fn mitochondrial_translate_test() {
+        let code = GeneticCode::new(2).unwrap();
+        let mt = Ribosome::with_code(AminoCat::new(), RNACat::new(), code, true);
+        let m = &mt.amino_c.morphisms;
+
+        // AUU starts and UGA is Trp; AGA stops.
+        let strand = mt
+            .rna_c
+            .polymer_from_string(String::from("auugcuugaaga"))
+            .unwrap();
+        let segments = mt.translate(strand).unwrap();
+
+        match &segments[0] {
+            Segment::Protein(x) => assert_eq!(x.contents, vec![m.ala.clone(), m.trp.clone()]),
+            _ => panic!("Expected protein, got Junk in mitochondrial_translate_test!"),
+        }
+        assert_eq!(segments.len(), 1);
+
+        // The same table drives back translation.
+        assert_eq!(mt.amino_to_condon_vec(&m.stop).unwrap().len(), 4);
+        assert_eq!(mt.amino_to_condon_vec(&m.arg).unwrap().len(), 4);
+        assert_eq!(mt.amino_to_condon_vec(&m.start).unwrap().len(), 5);
+
+        let std = Ribosome::new(AminoCat::new(), RNACat::new());
+        let strand = std
+            .rna_c
+            .polymer_from_string(String::from("auugcuugaaga"))
+            .unwrap();
+        match &std.translate(strand).unwrap()[..] {
+            [] => (),
+            _ => panic!("Expected no protein under the standard code!"),
+        }
+
+        // Without alternative starts, AUU is junk under the same code.
+        let aug_only = Ribosome::with_code(AminoCat::new(), RNACat::new(), code, false);
+        let strand = aug_only
+            .rna_c
+            .polymer_from_string(String::from("auugcuugaaga"))
+            .unwrap();
+        match &aug_only.translate(strand).unwrap()[..] {
+            [] => (),
+            _ => panic!("Expected no protein without alternative starts!"),
+        }
+    }
+
+    #[test]
+    fn default_start_is_aug_only() {
+        let ribo = Ribosome::new(AminoCat::new(), RNACat::new());
+
+        // UUG and CUG are starts in table 1, but only when asked for.
+        let strand = ribo
+            .rna_c
+            .polymer_from_string(String::from("ccuuggcuaaauaacc"))
+            .unwrap();
+        match &ribo.translate(strand).unwrap()[..] {
+            [] => (),
+            _ => panic!("Expected Ribosome::new not to start at UUG!"),
+        }
+        let start = ribo
+            .amino_to_condon_vec(&ribo.amino_c.morphisms.start)
+            .unwrap();
+        assert_eq!(start, vec![ribo.aug()]);
+
+        let alt = Ribosome::with_code(
+            AminoCat::new(),
+            RNACat::new(),
+            GeneticCode::standard(),
+            true,
+        );
+        let strand = alt
+            .rna_c
+            .polymer_from_string(String::from("ccuuggcuaaauaacc"))
+            .unwrap();
+        match &alt.translate(strand).unwrap()[..] {
+            [Segment::Junk(_), Segment::Protein(x)] => assert_eq!(x.len(), 2),
+            _ => panic!("Expected UUG to start a protein with alternative starts!"),
+        }
+        assert_eq!(
+            alt.amino_to_condon_vec(&alt.amino_c.morphisms.start)
+                .unwrap()
+                .len(),
+            3
+        );
+    }
+
     #[test]
     fn junk_data_test() {
         let ac = AminoCat::new();
@@ -927,6 +654,43 @@ mod tests {
         }
     }
 
+    #[test]
+    fn ambiguous_amino_to_codon() {
+        let ribo = Ribosome::new(AminoCat::new(), RNACat::new());
+        let m = &ribo.amino_c.morphisms;
+
+        // Sec and Pyl are read through the UGA and UAG stops.
+        let sec = ribo.amino_to_condon_vec(&m.sec).unwrap();
+        assert_eq!(sec.len(), 1);
+        assert_eq!(ribo.codon_to_amino(&sec[0]), m.stop);
+        let pyl = ribo.amino_to_condon_vec(&m.pyl).unwrap();
+        assert_eq!(ribo.codon_to_amino(&pyl[0]), m.stop);
+        assert_ne!(sec, pyl);
+
+        let asx = ribo.amino_to_condon_vec(&m.asx).unwrap();
+        assert_eq!(asx.len(), 4);
+        assert!(asx
+            .iter()
+            .all(|r| [&m.asn, &m.asp].contains(&&ribo.codon_to_amino(r))));
+
+        let xle = ribo.amino_to_condon_vec(&m.xle).unwrap();
+        assert_eq!(xle.len(), 9);
+        assert_eq!(ribo.amino_to_condon_vec(&m.glx).unwrap().len(), 4);
+
+        // Every codon of the twenty residues.
+        let xaa = ribo.amino_to_condon_vec(&m.xaa).unwrap();
+        let each: usize = Amino::STANDARD
+            .iter()
+            .map(|a| {
+                ribo.amino_to_condon_vec(&ribo.amino_c.intern(a))
+                    .unwrap()
+                    .len()
+            })
+            .sum();
+        assert_eq!(xaa.len(), each);
+        assert!(xaa.iter().all(|r| ribo.codon_to_amino(r) != m.stop));
+    }
+
     #[test]
     fn protein_to_sources() {
         let ribo = Ribosome::new(AminoCat::new(), RNACat::new());
@@ -1007,6 +771,40 @@ mod tests {
                 ribo.rna_c.a.clone(),
                 ribo.rna_c.g.clone(),
             ],
+            // Base 5
+            vec![
+                ribo.rna_c.a.clone(),
+                ribo.rna_c.g.clone(),
+                ribo.rna_c.a.clone(),
+                ribo.rna_c.c.clone(),
+                ribo.rna_c.a.clone(),
+                ribo.rna_c.a.clone(),
+            ],
+            vec![
+                ribo.rna_c.a.clone(),
+                ribo.rna_c.g.clone(),
+                ribo.rna_c.a.clone(),
+                ribo.rna_c.c.clone(),
+                ribo.rna_c.a.clone(),
+                ribo.rna_c.g.clone(),
+            ],
+            // Base 6
+            vec![
+                ribo.rna_c.a.clone(),
+                ribo.rna_c.g.clone(),
+                ribo.rna_c.g.clone(),
+                ribo.rna_c.c.clone(),
+                ribo.rna_c.a.clone(),
+                ribo.rna_c.a.clone(),
+            ],
+            vec![
+                ribo.rna_c.a.clone(),
+                ribo.rna_c.g.clone(),
+                ribo.rna_c.g.clone(),
+                ribo.rna_c.c.clone(),
+                ribo.rna_c.a.clone(),
+                ribo.rna_c.g.clone(),
+            ],
         ];
 
         let all_vecs = ribo.protein_to_sources(&strand).unwrap();
@@ -1016,7 +814,8 @@ mod tests {
         // Deadly string.
         // let seg_string = String::from("aug aug acg gau cag ccg caa gcg gaa uug gcg uuu acg uac gau gcg ccg uaa".replace(" ", ""));
         // Non-deadly string.
-        let seg_string = String::from("aug aug acg gau cag ccg caa gcg gaa uug gcg uuu acg uac gau gcg uaa".replace(" ", ""));
+        let seg_string =
+            "aug aug acg gau cag ccg caa gcg gaa uug gcg uuu acg uac gau gcg uaa".replace(" ", "");
         let strand = ribo.rna_c.polymer_from_string(seg_string).unwrap();
 
         let protein = ribo.translate(strand).unwrap();
@@ -1024,12 +823,12 @@ mod tests {
         match &protein[0] {
             Segment::Junk(_) => panic!("Got Junk in protein to sources translate"),
             Segment::Protein(x) => {
-                let next_vecs = ribo.protein_to_sources(&x).unwrap();
+                let next_vecs = ribo.protein_to_sources(x).unwrap();
                 let mut result = 1;
 
                 for i in 0..x.contents.len() {
                     let next = ribo.amino_to_condon_vec(&x.contents[i]).unwrap().len();
-                    result = result * next;
+                    result *= next;
                 }
 
                 assert_eq!(
@@ -1039,4 +838,51 @@ mod tests {
             }
         }
     }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn par_plain_translate_test() {
+        let ribo = Ribosome::new(AminoCat::new(), RNACat::new());
+
+        let t_string = "augacggaucagccgcaagcggaauuggcguuuacguacgaugcgccguaa".repeat(2000);
+        let strand = ribo.rna_c.polymer_from_string(t_string.clone()).unwrap();
+        let seq = ribo.plain_translate(strand).unwrap();
+
+        let strand = ribo.rna_c.polymer_from_string(t_string).unwrap();
+        let par = ribo.par_plain_translate(strand).unwrap();
+
+        assert_eq!(par, seq);
+
+        let strand = ribo
+            .rna_c
+            .polymer_from_string(String::from("augc"))
+            .unwrap();
+        assert_eq!(ribo.par_plain_translate(strand), None);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn segments_serde_round_trip() {
+        let ribo = Ribosome::new(AminoCat::new(), RNACat::new());
+
+        let strand = ribo
+            .rna_c
+            .polymer_from_string(String::from("ccaugcaguaagg"))
+            .unwrap();
+        let segments = ribo.translate(strand).unwrap();
+
+        let json = serde_json::to_string(&segments).unwrap();
+        let mut de = serde_json::Deserializer::from_str(&json);
+        let back = ribo.deserialize_segments(&mut de).unwrap();
+
+        assert_eq!(back.len(), segments.len());
+        for (x, y) in back.iter().zip(segments.iter()) {
+            match (x, y) {
+                (Segment::Protein(a), Segment::Protein(b)) => assert_eq!(a, b),
+                (Segment::Junk(a), Segment::Junk(b)) => assert_eq!(a, b),
+                _ => panic!("segment kinds differ"),
+            }
+        }
+        assert_eq!(serde_json::to_string(&back).unwrap(), json);
+    }
 }
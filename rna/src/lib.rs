@@ -1,145 +1,219 @@
 #[macro_use]
 extern crate enum_display_derive;
 
-use category::{Cat, ICat, NCat};
-use monomer::{IMono, Mono, NucleicAcid};
-use once_mono::{IMonomer};
-use polymer::{Polymer, Strand};
-
+use alphabet::Alphabet;
+use monomer::Packed;
+use polymer::{PackedStrand, Polymer, Strand};
+
+#[cfg(feature = "parallel")]
+use category::ICat;
+#[cfg(feature = "parallel")]
+use polymer::PAR_CHUNK;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+use std::convert::TryFrom;
 use std::fmt::Display;
 
-// TODO: Support the other RNA alphabets.
-#[derive(Debug, Display, PartialEq, Clone)]
+#[derive(Debug, Display, PartialEq, Clone, Alphabet)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[alphabet(
+    name = "RNA (ACGU)",
+    cat = "RNACat",
+    cell = "RNACell",
+    polymer = "Strand",
+    nucleic
+)]
 pub enum RNA {
+    #[base(letter = 'A', complement = U)]
     A,
+    #[base(letter = 'U', complement = A)]
     U,
+    #[base(letter = 'G', complement = C, gc)]
     G,
+    #[base(letter = 'C', complement = G, gc)]
     C,
 }
 
-impl NucleicAcid for RNA {
-    fn is_g_or_c(&self) -> bool {
+impl Packed for RNA {
+    fn to_bits(&self) -> u8 {
         match self {
-            RNA::G => true,
-            RNA::C => true,
-            _ => false,
+            RNA::U => 0,
+            RNA::C => 1,
+            RNA::A => 2,
+            RNA::G => 3,
         }
     }
-}
 
-impl Mono for RNA {
-    fn from_string(s: String) -> Option<RNA> {
-        match s.to_uppercase().as_ref() {
-            "A" => Some(RNA::A),
-            "U" => Some(RNA::U),
-            "C" => Some(RNA::C),
-            "G" => Some(RNA::G),
-            _ => None,
+    fn from_bits(b: u8) -> Self {
+        match b & 0b11 {
+            0 => RNA::U,
+            1 => RNA::C,
+            2 => RNA::A,
+            _ => RNA::G,
         }
     }
 }
 
-impl IMono for RNA {
-    fn inverse(c: &Self) -> Self {
-        match c {
-            RNA::A => RNA::U,
-            RNA::U => RNA::A,
-            RNA::C => RNA::G,
-            RNA::G => RNA::C,
-        }
+impl RNACat {
+    pub fn monomer_from_bits(&self, b: u8) -> RNACell {
+        self.cell(&RNA::from_bits(b))
+    }
+
+    // NOTE: Unlike PackedStrand::to_strand, shares this Cat's cells.
+    pub fn unpack(&self, p: &PackedStrand<RNACell>) -> Strand<RNACell> {
+        (0..p.len())
+            .map(|i| self.monomer_from_bits(p.bits(i)))
+            .collect()
     }
 }
 
-pub type RNACell = IMonomer<RNA>;
+// NOTE: Same result as inverse_p, spread over rayon's thread pool.
+#[cfg(feature = "parallel")]
+impl RNACat {
+    pub fn par_inverse_p(&self, u: &Strand<RNACell>) -> Strand<RNACell> {
+        let contents = u
+            .contents
+            .par_iter()
+            .with_min_len(PAR_CHUNK)
+            .rev()
+            .map(|z| self.inverse_m(z))
+            .collect();
+
+        Strand { contents }
+    }
+}
 
-pub struct RNACat {
-    pub a: RNACell,
-    pub u: RNACell,
-    pub c: RNACell,
-    pub g: RNACell,
+// NOTE: IUPAC nucleotide codes, for sequences carrying ambiguous bases
+// such as those in predicted or degenerate transcripts. Only S is certain
+// to be a G or C, other ambiguity codes are not counted towards GC content.
+#[derive(Debug, Display, PartialEq, Clone, Alphabet)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[alphabet(
+    name = "IUPAC RNA (ACGURYSWKMBDHVN)",
+    cat = "IupacRNACat",
+    cell = "IupacRNACell",
+    polymer = "Strand",
+    nucleic
+)]
+pub enum IupacRNA {
+    #[base(letter = 'A', complement = U)]
+    A,
+    #[base(letter = 'C', complement = G, gc)]
+    C,
+    #[base(letter = 'G', complement = C, gc)]
+    G,
+    #[base(letter = 'U', complement = A)]
+    U,
+    #[base(letter = 'R', complement = Y)]
+    R,
+    #[base(letter = 'Y', complement = R)]
+    Y,
+    #[base(letter = 'S', complement = S, gc)]
+    S,
+    #[base(letter = 'W', complement = W)]
+    W,
+    #[base(letter = 'K', complement = M)]
+    K,
+    #[base(letter = 'M', complement = K)]
+    M,
+    #[base(letter = 'B', complement = V)]
+    B,
+    #[base(letter = 'D', complement = H)]
+    D,
+    #[base(letter = 'H', complement = D)]
+    H,
+    #[base(letter = 'V', complement = B)]
+    V,
+    #[base(letter = 'N', complement = N)]
+    N,
 }
 
-impl Cat<RNACell, Strand<RNACell>> for RNACat {
-    fn new() -> Self {
-        RNACat {
-            a: IMonomer::from_string(String::from("a")).unwrap(),
-            u: IMonomer::from_string(String::from("u")).unwrap(),
-            c: IMonomer::from_string(String::from("c")).unwrap(),
-            g: IMonomer::from_string(String::from("g")).unwrap(),
+impl IupacRNA {
+    // The strict bases this code may stand for.
+    pub fn bases(&self) -> Vec<RNA> {
+        match self {
+            IupacRNA::A => vec![RNA::A],
+            IupacRNA::C => vec![RNA::C],
+            IupacRNA::G => vec![RNA::G],
+            IupacRNA::U => vec![RNA::U],
+            IupacRNA::R => vec![RNA::A, RNA::G],
+            IupacRNA::Y => vec![RNA::C, RNA::U],
+            IupacRNA::S => vec![RNA::C, RNA::G],
+            IupacRNA::W => vec![RNA::A, RNA::U],
+            IupacRNA::K => vec![RNA::G, RNA::U],
+            IupacRNA::M => vec![RNA::A, RNA::C],
+            IupacRNA::B => vec![RNA::C, RNA::G, RNA::U],
+            IupacRNA::D => vec![RNA::A, RNA::G, RNA::U],
+            IupacRNA::H => vec![RNA::A, RNA::C, RNA::U],
+            IupacRNA::V => vec![RNA::A, RNA::C, RNA::G],
+            IupacRNA::N => vec![RNA::A, RNA::C, RNA::G, RNA::U],
         }
     }
 
-    fn monomer_from_string(&self, s: String) -> Option<RNACell> {
-        match RNA::from_string(s) {
-            None => None,
-            Some(x) => match x {
-                RNA::A => Some(self.a.clone()),
-                RNA::U => Some(self.u.clone()),
-                RNA::C => Some(self.c.clone()),
-                RNA::G => Some(self.g.clone()),
-            },
-        }
+    pub fn matches(&self, d: &RNA) -> bool {
+        self.bases().contains(d)
     }
+}
 
-    fn polymer_from_string(&self, s: String) -> Option<Strand<RNACell>> {
-        let mut x = Strand::<RNACell>::new();
-        let mut ok = true;
-
-        for c in s.chars() {
-            match self.monomer_from_string(c.to_string()) {
-                Some(y) => x.push(y),
-                None => {
-                    ok = false;
-                    break;
-                }
-            }
-        }
-
-        match ok {
-            true => Some(x),
-            _ => None,
+impl From<RNA> for IupacRNA {
+    fn from(d: RNA) -> Self {
+        match d {
+            RNA::A => IupacRNA::A,
+            RNA::U => IupacRNA::U,
+            RNA::C => IupacRNA::C,
+            RNA::G => IupacRNA::G,
         }
     }
 }
 
-impl ICat<RNACell, Strand<RNACell>> for RNACat {
-    fn inverse_m(&self, t: &RNACell) -> RNACell {
-        match t.read().as_ref().unwrap() {
-            RNA::A => self.u.clone(),
-            RNA::U => self.a.clone(),
-            RNA::C => self.g.clone(),
-            RNA::G => self.c.clone(),
+// NOTE: Fails with the ambiguous code that could not be narrowed.
+impl TryFrom<IupacRNA> for RNA {
+    type Error = IupacRNA;
+
+    fn try_from(d: IupacRNA) -> Result<Self, Self::Error> {
+        match d {
+            IupacRNA::A => Ok(RNA::A),
+            IupacRNA::U => Ok(RNA::U),
+            IupacRNA::C => Ok(RNA::C),
+            IupacRNA::G => Ok(RNA::G),
+            x => Err(x),
         }
     }
+}
 
-    fn inverse_p(&self, u: &Strand<RNACell>) -> Strand<RNACell> {
-        let mut y = Strand::<RNACell>::new();
-        for z in &u.contents {
-            y.push(self.inverse_m(&z));
+impl IupacRNACat {
+    pub fn from_strict(&self, u: &Strand<RNACell>) -> Strand<IupacRNACell> {
+        let mut y = Strand::<IupacRNACell>::new();
+        for z in u {
+            let x = IupacRNA::from(z.read().as_ref().unwrap().clone());
+            y.push(self.cell(&x));
         }
-        y.contents =  y.contents.into_iter().rev().collect();
         y
     }
-}
 
-impl NCat<RNACell, Strand<RNACell>> for RNACat {
-    fn gc_content(u: Strand<RNACell>) -> (u64, u64) {
-        let mut d = 0;
-        let mut n = 0;
-        for x in u.contents {
-            d = d + 1;
-            if RNACell::is_g_or_c(&x) {
-                n = n + 1;
-            }
+    // NOTE: None if any base is ambiguous.
+    pub fn to_strict(&self, u: &Strand<IupacRNACell>, c: &RNACat) -> Option<Strand<RNACell>> {
+        let mut y = Strand::<RNACell>::new();
+        for z in u {
+            let x = RNA::try_from(z.read().as_ref().unwrap().clone()).ok()?;
+            y.push(match x {
+                RNA::A => c.a.clone(),
+                RNA::U => c.u.clone(),
+                RNA::C => c.c.clone(),
+                RNA::G => c.g.clone(),
+            });
         }
-
-        (n, d)
+        Some(y)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use category::{Cat, ICat};
+    use monomer::{Mono, ParseError};
+    use polymer::IPolymer;
 
     #[test]
     fn rna_from_char() {
@@ -215,7 +289,15 @@ mod tests {
             &RNA::G
         );
 
-        assert_eq!(c.monomer_from_string(String::from("d")), None);
+        assert_eq!(
+            c.monomer_from_string(String::from("d")),
+            Err(ParseError::Monomer {
+                found: String::from("d"),
+                offset: 0,
+                alphabet: "RNA (ACGU)",
+            })
+        );
+        assert_eq!(c.maybe_monomer_from_string(String::from("d")), None);
     }
 
     // TODO Add Inverse:
@@ -248,13 +330,20 @@ mod tests {
         let maybe_none = c.polymer_from_string(bad_str);
 
         match maybe_h {
-            Some(_) => assert!(true),
-            None => panic!("Recieved 'None' on good input"),
+            Ok(_) => {}
+            Err(e) => panic!("Recieved 'Err({})' on good input", e),
         }
 
         match maybe_none {
-            None => assert!(true),
-            Some(x) => panic!("Recieved 'Some({:?})' on bad input", x),
+            Err(e) => assert_eq!(
+                e,
+                ParseError::Monomer {
+                    found: String::from("b"),
+                    offset: 0,
+                    alphabet: "RNA (ACGU)",
+                }
+            ),
+            Ok(x) => panic!("Recieved 'Ok({:?})' on bad input", x),
         }
     }
 
@@ -309,4 +398,103 @@ mod tests {
         assert_eq!(4, maybe_4);
         assert_eq!(5, maybe_5);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn iupac_from_string() {
+        let c = IupacRNACat::new();
+
+        let h = c.polymer_from_string(String::from("acguNNNNrykm")).unwrap();
+        assert_eq!(h.contents.len(), 12);
+        assert_eq!(h.contents[4], c.n);
+        assert_eq!(
+            c.polymer_from_string(String::from("acgx")).unwrap_err(),
+            ParseError::Monomer {
+                found: String::from("x"),
+                offset: 3,
+                alphabet: IupacRNA::ALPHABET,
+            }
+        );
+    }
+
+    #[test]
+    fn iupac_inverse() {
+        let c = IupacRNACat::new();
+
+        let h1 = c.polymer_from_string(String::from("ARYSWKMBDHVN")).unwrap();
+        let h2 = c.polymer_from_string(String::from("NBDHVKMWSRYU")).unwrap();
+
+        let i1 = c.inverse_p(&h1);
+        let i2 = c.inverse_p(&i1);
+
+        assert_eq!(i1, h2);
+        assert_eq!(i2, h1);
+    }
+
+    #[test]
+    fn iupac_strict_round_trip() {
+        let c = RNACat::new();
+        let ic = IupacRNACat::new();
+
+        let h1 = c.polymer_from_string(String::from("gauuaca")).unwrap();
+        let i1 = ic.from_strict(&h1);
+
+        assert_eq!(i1, ic.polymer_from_string(String::from("gauuaca")).unwrap());
+        assert_eq!(ic.to_strict(&i1, &c).unwrap(), h1);
+
+        let i2 = ic.polymer_from_string(String::from("gauuNca")).unwrap();
+        assert_eq!(ic.to_strict(&i2, &c), None);
+
+        assert_eq!(RNA::try_from(IupacRNA::R), Err(IupacRNA::R));
+        assert!(IupacRNA::R.matches(&RNA::G));
+        assert!(!IupacRNA::R.matches(&RNA::U));
+    }
+
+    #[test]
+    fn packed_round_trip() {
+        let c = RNACat::new();
+
+        let s = c.polymer_from_string(String::from("gauuacag")).unwrap();
+        let p = PackedStrand::from_strand(&s);
+
+        assert_eq!(p.as_bytes().len(), 2);
+        assert_eq!(c.unpack(&p), s);
+        assert_eq!(c.unpack(&p.inverse()), c.inverse_p(&s));
+        assert_eq!(p.gc_content(), s.gc_content());
+    }
+
+    #[test]
+    fn from_bytes() {
+        let c = RNACat::new();
+
+        assert_eq!(RNA::from_byte(b'g'), Ok(RNA::G));
+        assert_eq!(
+            c.polymer_from_bytes(b"GAUUACA").unwrap(),
+            c.polymer_from_string(String::from("gauuaca")).unwrap()
+        );
+        assert_eq!(
+            Strand::<RNACell>::from_bytes(b"gauuaca").unwrap(),
+            c.polymer_from_bytes(b"gauuaca").unwrap()
+        );
+        assert_eq!(
+            c.polymer_from_bytes(b"gaNuaca").unwrap_err(),
+            ParseError::Monomer {
+                found: String::from("N"),
+                offset: 2,
+                alphabet: RNA::ALPHABET,
+            }
+        );
+        assert!(IupacRNACat::new().polymer_from_bytes(b"gaNuaca").is_ok());
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn par_matches_sequential() {
+        let c = RNACat::new();
+
+        let h = c.polymer_from_string("gauuacaccg".repeat(5000)).unwrap();
+
+        assert_eq!(c.par_inverse_p(&h), c.inverse_p(&h));
+        assert_eq!(h.par_inverse(), h.inverse());
+        assert_eq!(h.par_gc_content(), h.gc_content());
+    }
+}
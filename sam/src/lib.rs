@@ -0,0 +1,600 @@
+use category::Cat;
+use dna::{DNACell, IupacDNACat, IupacDNACell};
+pub use format::FormatError;
+use format::Lines;
+use monomer::ParseError;
+use polymer::{Helix, IPolymer, Polymer, Strand};
+use std::error::Error;
+use std::fmt;
+use std::io::{self, BufRead};
+
+pub const PAIRED: u16 = 0x1;
+pub const PROPER_PAIR: u16 = 0x2;
+pub const UNMAPPED: u16 = 0x4;
+pub const MATE_UNMAPPED: u16 = 0x8;
+pub const REVERSE: u16 = 0x10;
+pub const MATE_REVERSE: u16 = 0x20;
+pub const FIRST: u16 = 0x40;
+pub const LAST: u16 = 0x80;
+pub const SECONDARY: u16 = 0x100;
+pub const QC_FAIL: u16 = 0x200;
+pub const DUPLICATE: u16 = 0x400;
+pub const SUPPLEMENTARY: u16 = 0x800;
+
+#[derive(Debug)]
+pub enum SamError {
+    Read(FormatError),
+    // A SEQ column that is not IUPAC DNA, the error's offset is into it.
+    Parse { line: usize, error: ParseError },
+}
+
+impl fmt::Display for SamError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SamError::Read(e) => write!(f, "{}", e),
+            SamError::Parse { line, error } => write!(f, "line {} column 10: {}", line, error),
+        }
+    }
+}
+
+impl Error for SamError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            SamError::Read(e) => Some(e),
+            SamError::Parse { error, .. } => Some(error),
+        }
+    }
+}
+
+impl From<FormatError> for SamError {
+    fn from(e: FormatError) -> Self {
+        SamError::Read(e)
+    }
+}
+
+impl From<io::Error> for SamError {
+    fn from(e: io::Error) -> Self {
+        SamError::Read(e.into())
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Op {
+    // M, a match or mismatch.
+    Match,
+    // I
+    Insertion,
+    // D
+    Deletion,
+    // N, an intron or other skipped stretch of reference.
+    Skip,
+    // S
+    SoftClip,
+    // H
+    HardClip,
+    // P
+    Padding,
+    // =
+    Equal,
+    // X
+    Diff,
+}
+
+impl Op {
+    pub fn from_byte(b: u8) -> Option<Self> {
+        match b {
+            b'M' => Some(Op::Match),
+            b'I' => Some(Op::Insertion),
+            b'D' => Some(Op::Deletion),
+            b'N' => Some(Op::Skip),
+            b'S' => Some(Op::SoftClip),
+            b'H' => Some(Op::HardClip),
+            b'P' => Some(Op::Padding),
+            b'=' => Some(Op::Equal),
+            b'X' => Some(Op::Diff),
+            _ => None,
+        }
+    }
+
+    pub fn consumes_query(self) -> bool {
+        matches!(
+            self,
+            Op::Match | Op::Insertion | Op::SoftClip | Op::Equal | Op::Diff
+        )
+    }
+
+    pub fn consumes_reference(self) -> bool {
+        matches!(
+            self,
+            Op::Match | Op::Deletion | Op::Skip | Op::Equal | Op::Diff
+        )
+    }
+}
+
+fn cigar(s: &str) -> Option<Vec<(Op, usize)>> {
+    if s == "*" {
+        return Some(Vec::new());
+    }
+
+    let mut x = Vec::new();
+    let mut n = 0;
+    let mut digits = false;
+    for b in s.bytes() {
+        match b {
+            b'0'..=b'9' => {
+                n = n * 10 + (b - b'0') as usize;
+                digits = true;
+            }
+            _ if digits => {
+                x.push((Op::from_byte(b)?, n));
+                n = 0;
+                digits = false;
+            }
+            _ => return None,
+        }
+    }
+
+    match digits || x.is_empty() {
+        true => None,
+        _ => Some(x),
+    }
+}
+
+// NOTE: Columns of a read laid against the reference, (reference, read).
+// Insertions have no reference base and deletions and skips no read base.
+// Clipped and padded bases are left out. start and end are the 0-based,
+// half open span of reference covered. Read bases keep their IUPAC codes.
+#[derive(Debug, PartialEq)]
+pub struct Alignment {
+    pub start: usize,
+    pub end: usize,
+    pub pairs: Vec<(Option<DNACell>, Option<IupacDNACell>)>,
+}
+
+impl Alignment {
+    // 0-based reference positions where a read base cannot be the
+    // reference's. An ambiguous code such as N matches any base it may
+    // stand for.
+    pub fn mismatches(&self) -> Vec<usize> {
+        let mut r = self.start;
+        let mut next = Vec::new();
+
+        for (x, y) in self.pairs.iter() {
+            if let (Some(x), Some(y)) = (x, y) {
+                if let (Some(x), Some(y)) = (x.read().as_ref(), y.read().as_ref()) {
+                    if !y.matches(x) {
+                        next.push(r);
+                    }
+                }
+            }
+            if x.is_some() {
+                r += 1;
+            }
+        }
+
+        next
+    }
+}
+
+// NOTE: pos is 1-based as in the file, 0 where there is none. seq and qual
+// read as the read was sequenced, so a REVERSE record's have been turned
+// back from the reference's strand. qual is Phred scores, empty for '*'.
+#[derive(Debug, PartialEq)]
+pub struct Record {
+    pub qname: String,
+    pub flag: u16,
+    pub rname: Option<String>,
+    pub pos: usize,
+    // None where the file has 255, unavailable.
+    pub mapq: Option<u8>,
+    pub cigar: Vec<(Op, usize)>,
+    pub rnext: Option<String>,
+    pub pnext: usize,
+    pub tlen: i64,
+    pub seq: Strand<IupacDNACell>,
+    pub qual: Vec<u8>,
+    // (tag, type, value), as in NM:i:0.
+    pub tags: Vec<(String, char, String)>,
+}
+
+impl Record {
+    pub fn has(&self, flag: u16) -> bool {
+        self.flag & flag != 0
+    }
+
+    pub fn is_reverse(&self) -> bool {
+        self.has(REVERSE)
+    }
+
+    pub fn is_unmapped(&self) -> bool {
+        self.has(UNMAPPED)
+    }
+
+    pub fn tag(&self, tag: &str) -> Option<&str> {
+        self.tags
+            .iter()
+            .find(|(t, _, _)| t == tag)
+            .map(|(_, _, v)| v.as_str())
+    }
+
+    // The read as it lies on the reference's forward strand, as in the file.
+    pub fn forward_seq(&self) -> Strand<IupacDNACell> {
+        match self.is_reverse() {
+            true => self.seq.inverse(),
+            _ => self.seq.subsequence(..).unwrap(),
+        }
+    }
+
+    // The 0-based, half open span of reference the read covers. None if
+    // it is unmapped or has no CIGAR.
+    pub fn reference_span(&self) -> Option<(usize, usize)> {
+        if self.is_unmapped() || self.pos == 0 || self.cigar.is_empty() {
+            return None;
+        }
+
+        let len: usize = self
+            .cigar
+            .iter()
+            .filter(|(op, _)| op.consumes_reference())
+            .map(|(_, n)| n)
+            .sum();
+
+        Some((self.pos - 1, self.pos - 1 + len))
+    }
+
+    // NOTE: reference is the whole of rname. None where reference_span is,
+    // if there is no SEQ, if the CIGAR does not cover SEQ, or if the span
+    // runs off the end of reference.
+    pub fn align(&self, reference: &Helix<DNACell>) -> Option<Alignment> {
+        let (start, end) = self.reference_span()?;
+        let seq = self.forward_seq();
+        let query: usize = self
+            .cigar
+            .iter()
+            .filter(|(op, _)| op.consumes_query())
+            .map(|(_, n)| n)
+            .sum();
+        if seq.is_empty() || query != seq.len() || end > reference.len() {
+            return None;
+        }
+
+        let mut r = start;
+        let mut q = 0;
+        let mut pairs = Vec::new();
+        for (op, n) in self.cigar.iter() {
+            let n = *n;
+            match op {
+                Op::Match | Op::Equal | Op::Diff => {
+                    pairs.extend((0..n).map(|i| (reference.get(r + i), seq.get(q + i))));
+                }
+                Op::Insertion => pairs.extend((0..n).map(|i| (None, seq.get(q + i)))),
+                Op::Deletion | Op::Skip => {
+                    pairs.extend((0..n).map(|i| (reference.get(r + i), None)))
+                }
+                Op::SoftClip | Op::HardClip | Op::Padding => {}
+            }
+            if op.consumes_reference() {
+                r += n;
+            }
+            if op.consumes_query() {
+                q += n;
+            }
+        }
+
+        Some(Alignment { start, end, pairs })
+    }
+}
+
+fn record(l: &str, line: usize, cat: &IupacDNACat) -> Result<Record, SamError> {
+    let columns: Vec<&str> = l.split('\t').collect();
+    let err = |column: usize, expected: &'static str| {
+        SamError::from(FormatError::Format {
+            line,
+            column,
+            expected,
+        })
+    };
+
+    if columns.len() < 11 {
+        return Err(err(
+            columns.len() + 1,
+            "at least eleven tab separated columns",
+        ));
+    }
+
+    let name = |s: &str| match s {
+        "*" => None,
+        _ => Some(s.to_string()),
+    };
+
+    let flag: u16 = columns[1].parse().map_err(|_| err(2, "a flag"))?;
+    let pos = columns[3].parse().map_err(|_| err(4, "a position"))?;
+    let mapq = match columns[4]
+        .parse()
+        .map_err(|_| err(5, "a mapping quality"))?
+    {
+        255 => None,
+        x => Some(x),
+    };
+    let cigar = cigar(columns[5]).ok_or_else(|| err(6, "a CIGAR or '*'"))?;
+    let rnext = match columns[6] {
+        "=" => name(columns[2]),
+        x => name(x),
+    };
+    let pnext = columns[7].parse().map_err(|_| err(8, "a mate position"))?;
+    let tlen = columns[8]
+        .parse()
+        .map_err(|_| err(9, "a template length"))?;
+
+    let mut seq = match columns[9] {
+        "*" => Strand::new(),
+        x => {
+            cat.polymer_from_string(x.to_string())
+                .map_err(|error| SamError::Parse { line, error })?
+                .strand
+        }
+    };
+
+    let mut qual = match columns[10] {
+        "*" => Vec::new(),
+        x => x
+            .bytes()
+            .map(|b| b.checked_sub(33))
+            .collect::<Option<_>>()
+            .ok_or_else(|| err(11, "Phred+33 qualities"))?,
+    };
+    if !qual.is_empty() && qual.len() != seq.len() {
+        return Err(err(11, "a quality for every base"));
+    }
+
+    let tags = columns[11..]
+        .iter()
+        .enumerate()
+        .map(|(i, x)| {
+            let t: Vec<&str> = x.splitn(3, ':').collect();
+            match &t[..] {
+                [tag, ty, value] if tag.len() == 2 && ty.len() == 1 => Ok((
+                    tag.to_string(),
+                    ty.chars().next().unwrap(),
+                    value.to_string(),
+                )),
+                _ => Err(err(12 + i, "a TAG:TYPE:VALUE field")),
+            }
+        })
+        .collect::<Result<_, _>>()?;
+
+    if flag & REVERSE != 0 {
+        seq = seq.inverse();
+        qual.reverse();
+    }
+
+    Ok(Record {
+        qname: columns[0].to_string(),
+        flag,
+        rname: name(columns[2]),
+        pos,
+        mapq,
+        cigar,
+        rnext,
+        pnext,
+        tlen,
+        seq,
+        qual,
+        tags,
+    })
+}
+
+// NOTE: The '@' header is read by new, then records stream. Each record is
+// a line of its own, so a bad one is an error and reading goes on with the
+// next, only an io error ends the stream. SEQ is read as IUPAC DNA, so N
+// and the other ambiguity codes are kept, but '=' for a base equal to the
+// reference's is not read and is a Parse error.
+pub struct Reader<'a, R> {
+    lines: Lines<R>,
+    cat: &'a IupacDNACat,
+    header: Vec<String>,
+}
+
+impl<'a, R: BufRead> Reader<'a, R> {
+    pub fn new(inner: R, cat: &'a IupacDNACat) -> Result<Self, SamError> {
+        let mut lines = Lines::new(inner);
+        let mut header = Vec::new();
+
+        while lines.get_mut().fill_buf()?.first() == Some(&b'@') {
+            match lines.next() {
+                Some(x) => header.push(x?.1),
+                None => break,
+            }
+        }
+
+        Ok(Reader { lines, cat, header })
+    }
+
+    pub fn header(&self) -> &[String] {
+        &self.header
+    }
+
+    // (SN, LN) of each @SQ line.
+    pub fn references(&self) -> Vec<(String, usize)> {
+        self.header
+            .iter()
+            .filter(|x| x.starts_with("@SQ\t"))
+            .filter_map(|x| {
+                let field = |k: &str| x.split('\t').find_map(|y| y.strip_prefix(k));
+                Some((field("SN:")?.to_string(), field("LN:")?.parse().ok()?))
+            })
+            .collect()
+    }
+}
+
+impl<'a, R: BufRead> Iterator for Reader<'a, R> {
+    type Item = Result<Record, SamError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for x in self.lines.by_ref() {
+            let (n, l) = match x {
+                Ok(y) => y,
+                Err(e) => return Some(Err(e.into())),
+            };
+
+            if l.trim().is_empty() {
+                continue;
+            }
+
+            return Some(record(&l, n, self.cat));
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dna::DNACat;
+
+    //                    0         1         2
+    //                    0123456789012345678901234
+    const GENOME: &str = "GGATCCATGACCTTGAGGAACTAGC";
+
+    const INPUT: &str = "\
+@HD\tVN:1.6\tSO:coordinate
+@SQ\tSN:chr1\tLN:25
+r1\t99\tchr1\t3\t60\t2S4M2I3M1D2M\t=\t12\t20\tTTATCCTTTTGCC\tIIIIIIIIIII#I\tNM:i:4\tRG:Z:g1
+r2\t147\tchr1\t12\t255\t5M3N2M\t=\t3\t-20\tCTTGAAC\t!\"#$%&'\tNM:i:0
+r3\t4\t*\t0\t0\t*\t*\t0\t0\tACGT\t*
+";
+
+    fn read(input: &str) -> Vec<Result<Record, SamError>> {
+        let c = IupacDNACat::new();
+        Reader::new(input.as_bytes(), &c).unwrap().collect()
+    }
+
+    fn strand(s: &str) -> Strand<IupacDNACell> {
+        IupacDNACat::new()
+            .polymer_from_string(String::from(s))
+            .unwrap()
+            .strand
+    }
+
+    #[test]
+    fn read_records() {
+        let c = IupacDNACat::new();
+        let r = Reader::new(INPUT.as_bytes(), &c).unwrap();
+        assert_eq!(r.header().len(), 2);
+        assert_eq!(r.references(), vec![(String::from("chr1"), 25)]);
+
+        let x: Vec<Record> = r.collect::<Result<_, _>>().unwrap();
+        assert_eq!(x.len(), 3);
+
+        assert_eq!(x[0].flag, PAIRED | PROPER_PAIR | MATE_REVERSE | FIRST);
+        assert_eq!((x[0].pos, x[0].mapq), (3, Some(60)));
+        assert_eq!(x[0].rnext, Some(String::from("chr1")));
+        assert_eq!(x[0].tag("RG"), Some("g1"));
+        assert_eq!(x[0].qual[11], 2);
+        assert_eq!(x[0].cigar[1], (Op::Match, 4));
+        assert_eq!(x[0].reference_span(), Some((2, 12)));
+
+        // Reverse reads come back as sequenced.
+        assert!(x[1].is_reverse());
+        assert_eq!(x[1].mapq, None);
+        assert_eq!(x[1].seq, strand("GTTCAAG"));
+        assert_eq!(x[1].forward_seq(), strand("CTTGAAC"));
+        assert_eq!(x[1].qual, vec![6, 5, 4, 3, 2, 1, 0]);
+        assert_eq!(x[1].reference_span(), Some((11, 21)));
+
+        assert!(x[2].is_unmapped());
+        assert_eq!(x[2].rname, None);
+        assert!(x[2].cigar.is_empty());
+        assert_eq!(x[2].reference_span(), None);
+    }
+
+    #[test]
+    fn read_errors() {
+        match &read("r\t0\tchr1\t1\t60\t4Q\t*\t0\t0\tACGT\t*\n")[..] {
+            [Err(SamError::Read(FormatError::Format {
+                line: 1, column: 6, ..
+            }))] => {}
+            y => panic!("expected a format error, got {:?}", y),
+        }
+        match &read("@HD\tVN:1.6\nr\t0\tchr1\t1\t60\t4M\t*\t0\t0\tACXT\t*\n")[..] {
+            [Err(SamError::Parse {
+                line: 2,
+                error: ParseError::Monomer { offset: 2, .. },
+            })] => {}
+            y => panic!("expected a parse error, got {:?}", y),
+        }
+        match &read("r\t0\tchr1\t1\t60\t4M\t*\t0\t0\tACGT\tIII\n")[..] {
+            [Err(SamError::Read(FormatError::Format {
+                line: 1,
+                column: 11,
+                ..
+            }))] => {}
+            y => panic!("expected a format error, got {:?}", y),
+        }
+        match &read("r\t0\tchr1\t1\t60\t4M\t*\t0\n")[..] {
+            [Err(SamError::Read(FormatError::Format {
+                line: 1, column: 9, ..
+            }))] => {}
+            y => panic!("expected a format error, got {:?}", y),
+        }
+
+        let x = read(concat!(
+            "a\t0\tchr1\t1\t60\t4M\t*\t0\t0\tAC=T\t*\n",
+            "b\t0\tchr1\t1\t60\t4M\t*\t0\n",
+            "c\t0\tchr1\t1\t60\t4M\t*\t0\t0\tACGT\t*\n",
+        ));
+        assert_eq!(x.len(), 3);
+        match &x[0] {
+            Err(SamError::Parse { line: 1, .. }) => {}
+            y => panic!("expected a parse error, got {:?}", y),
+        }
+        match &x[1] {
+            Err(SamError::Read(FormatError::Format { line: 2, .. })) => {}
+            y => panic!("expected a format error, got {:?}", y),
+        }
+        assert_eq!(x[2].as_ref().unwrap().qname, "c");
+    }
+
+    #[test]
+    fn align_records() {
+        let c = DNACat::new();
+        let genome = c.polymer_from_string(String::from(GENOME)).unwrap();
+        let x: Vec<Record> = read(INPUT).into_iter().collect::<Result<_, _>>().unwrap();
+
+        fn letter<T: fmt::Display>(x: &Option<T>) -> char {
+            x.as_ref()
+                .map_or('-', |y| y.to_string().chars().next().unwrap())
+        }
+        let column = |p: &(Option<DNACell>, Option<IupacDNACell>)| (letter(&p.0), letter(&p.1));
+
+        let a = x[0].align(&genome).unwrap();
+        assert_eq!((a.start, a.end), (2, 12));
+        let (r, q): (String, String) = a.pairs.iter().map(column).unzip();
+        assert_eq!(r, "ATCC--ATGACC");
+        assert_eq!(q, "ATCCTTTTG-CC");
+        assert_eq!(a.mismatches(), vec![6]);
+
+        let a = x[1].align(&genome).unwrap();
+        assert_eq!((a.start, a.end), (11, 21));
+        let (r, q): (String, String) = a.pairs.iter().map(column).unzip();
+        assert_eq!(r, "CTTGAGGAAC");
+        assert_eq!(q, "CTTGA---AC");
+        assert!(a.mismatches().is_empty());
+
+        assert_eq!(x[2].align(&genome), None);
+        let short = c.polymer_from_string(String::from("GGATCC")).unwrap();
+        assert_eq!(x[0].align(&short), None);
+
+        // N and other ambiguity codes are kept, and only count against
+        // reference bases they cannot stand for.
+        let x = read("n\t0\tchr1\t3\t60\t6M\t*\t0\t0\tANCRAT\t*\n");
+        let x = x[0].as_ref().unwrap();
+        assert_eq!(x.seq, strand("ANCRAT"));
+        let a = x.align(&genome).unwrap();
+        let (r, q): (String, String) = a.pairs.iter().map(column).unzip();
+        assert_eq!(r, "ATCCAT");
+        assert_eq!(q, "ANCRAT");
+        assert_eq!(a.mismatches(), vec![5]);
+    }
+}
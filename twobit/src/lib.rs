@@ -0,0 +1,508 @@
+use dna::{DNACat, DNACell, IupacDNA, IupacDNACat, IupacDNACell, DNA};
+use monomer::Packed;
+use polymer::{Helix, PackedStrand, Polymer};
+use std::convert::TryFrom;
+use std::error::Error;
+use std::fmt;
+use std::io::{self, Read, Seek, SeekFrom, Write};
+
+const SIGNATURE: u32 = 0x1A41_2743;
+
+#[derive(Debug)]
+pub enum TwoBitError {
+    Io(io::Error),
+    // The file is not laid out as a .2bit file should be.
+    Format {
+        expected: &'static str,
+    },
+    // No sequence with this name in the index.
+    Missing {
+        name: String,
+    },
+    // A [start, end) range that does not fit inside the sequence.
+    Range {
+        start: usize,
+        end: usize,
+        len: usize,
+    },
+}
+
+impl fmt::Display for TwoBitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TwoBitError::Io(e) => write!(f, "{}", e),
+            TwoBitError::Format { expected } => {
+                write!(f, "not a .2bit file: expected {}", expected)
+            }
+            TwoBitError::Missing { name } => write!(f, "no sequence named {:?}", name),
+            TwoBitError::Range { start, end, len } => write!(
+                f,
+                "range {}..{} is outside a sequence of length {}",
+                start, end, len
+            ),
+        }
+    }
+}
+
+impl Error for TwoBitError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            TwoBitError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for TwoBitError {
+    fn from(e: io::Error) -> Self {
+        TwoBitError::Io(e)
+    }
+}
+
+// NOTE: Blocks here and in the file are (start, size) pairs.
+#[derive(Debug, PartialEq, Clone)]
+struct Layout {
+    len: usize,
+    n: Vec<(usize, usize)>,
+    mask: Vec<(usize, usize)>,
+    // Where the packed bases begin.
+    dna: u64,
+}
+
+// The part of each block that falls in [start, end), moved to start at 0.
+fn clip(blocks: &[(usize, usize)], start: usize, end: usize) -> Vec<(usize, usize)> {
+    blocks
+        .iter()
+        .filter_map(|(s, n)| {
+            let lo = (*s).max(start);
+            let hi = (s + n).min(end);
+            match lo < hi {
+                true => Some((lo - start, hi - lo)),
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+// NOTE: Bases under an N-block hold whatever bits the file stored there,
+// usually T. n and mask say which bases to read as N or as lowercase,
+// relative to the start of the range fetched.
+#[derive(Debug, PartialEq)]
+pub struct Region {
+    pub seq: Helix<DNACell>,
+    pub n: Vec<(usize, usize)>,
+    pub mask: Vec<(usize, usize)>,
+}
+
+// NOTE: Only the header and index are read up front. Each sequence's
+// blocks are read the first time it is asked for, and bases only as
+// fetched, so memory stays small whatever the size of the genome.
+pub struct Reader<R> {
+    inner: R,
+    big_endian: bool,
+    names: Vec<String>,
+    offsets: Vec<u64>,
+    layouts: Vec<Option<Layout>>,
+}
+
+impl<R: Read + Seek> Reader<R> {
+    pub fn new(mut inner: R) -> Result<Self, TwoBitError> {
+        let mut b = [0u8; 4];
+        inner.read_exact(&mut b)?;
+        let big_endian = match (u32::from_le_bytes(b), u32::from_be_bytes(b)) {
+            (SIGNATURE, _) => false,
+            (_, SIGNATURE) => true,
+            _ => {
+                return Err(TwoBitError::Format {
+                    expected: "the .2bit signature",
+                })
+            }
+        };
+
+        let mut x = Reader {
+            inner,
+            big_endian,
+            names: Vec::new(),
+            offsets: Vec::new(),
+            layouts: Vec::new(),
+        };
+
+        let version = x.u32()?;
+        if version > 1 {
+            return Err(TwoBitError::Format {
+                expected: "version 0 or 1",
+            });
+        }
+        let count = x.u32()? as usize;
+        x.u32()?;
+
+        for _ in 0..count {
+            let mut len = [0u8; 1];
+            x.inner.read_exact(&mut len)?;
+            let mut name = vec![0u8; len[0] as usize];
+            x.inner.read_exact(&mut name)?;
+
+            // Version 1 files have 64 bit offsets, to pass 4GB.
+            let offset = match version {
+                0 => x.u32()? as u64,
+                _ => x.u64()?,
+            };
+
+            x.names.push(String::from_utf8_lossy(&name).into_owned());
+            x.offsets.push(offset);
+            x.layouts.push(None);
+        }
+
+        Ok(x)
+    }
+
+    fn u32(&mut self) -> io::Result<u32> {
+        let mut b = [0u8; 4];
+        self.inner.read_exact(&mut b)?;
+        Ok(match self.big_endian {
+            true => u32::from_be_bytes(b),
+            _ => u32::from_le_bytes(b),
+        })
+    }
+
+    fn u64(&mut self) -> io::Result<u64> {
+        let mut b = [0u8; 8];
+        self.inner.read_exact(&mut b)?;
+        Ok(match self.big_endian {
+            true => u64::from_be_bytes(b),
+            _ => u64::from_le_bytes(b),
+        })
+    }
+
+    fn blocks(&mut self) -> io::Result<Vec<(usize, usize)>> {
+        let count = self.u32()? as usize;
+        let mut starts = Vec::with_capacity(count);
+        for _ in 0..count {
+            starts.push(self.u32()? as usize);
+        }
+
+        let mut x = Vec::with_capacity(count);
+        for s in starts {
+            x.push((s, self.u32()? as usize));
+        }
+
+        Ok(x)
+    }
+
+    fn index(&self, name: &str) -> Result<usize, TwoBitError> {
+        self.names
+            .iter()
+            .position(|x| x == name)
+            .ok_or_else(|| TwoBitError::Missing {
+                name: name.to_string(),
+            })
+    }
+
+    fn layout(&mut self, i: usize) -> Result<&Layout, TwoBitError> {
+        if self.layouts[i].is_none() {
+            self.inner.seek(SeekFrom::Start(self.offsets[i]))?;
+            let len = self.u32()? as usize;
+            let n = self.blocks()?;
+            let mask = self.blocks()?;
+            self.u32()?;
+            let dna = self.inner.stream_position()?;
+
+            self.layouts[i] = Some(Layout { len, n, mask, dna });
+        }
+
+        Ok(self.layouts[i].as_ref().unwrap())
+    }
+
+    pub fn names(&self) -> &[String] {
+        &self.names
+    }
+
+    pub fn len(&mut self, name: &str) -> Result<usize, TwoBitError> {
+        let i = self.index(name)?;
+        Ok(self.layout(i)?.len)
+    }
+
+    // 2-bit codes for [start, end), read straight from where they sit.
+    fn bits(
+        &mut self,
+        name: &str,
+        start: usize,
+        end: usize,
+    ) -> Result<(Vec<u8>, Layout), TwoBitError> {
+        let i = self.index(name)?;
+        let l = self.layout(i)?.clone();
+        if start > end || end > l.len {
+            return Err(TwoBitError::Range {
+                start,
+                end,
+                len: l.len,
+            });
+        }
+
+        let mut bytes = vec![0u8; end.div_ceil(4) - start / 4];
+        self.inner
+            .seek(SeekFrom::Start(l.dna + (start / 4) as u64))?;
+        self.inner.read_exact(&mut bytes)?;
+
+        let x = (start..end)
+            .map(|j| {
+                let b = bytes[j / 4 - start / 4];
+                (b >> (6 - 2 * (j % 4))) & 0b11
+            })
+            .collect();
+
+        Ok((x, l))
+    }
+
+    pub fn fetch(
+        &mut self,
+        name: &str,
+        start: usize,
+        end: usize,
+        cat: &DNACat,
+    ) -> Result<Region, TwoBitError> {
+        let (bits, l) = self.bits(name, start, end)?;
+
+        Ok(Region {
+            seq: bits.into_iter().map(|b| cat.monomer_from_bits(b)).collect(),
+            n: clip(&l.n, start, end),
+            mask: clip(&l.mask, start, end),
+        })
+    }
+
+    // NOTE: As fetch, with the N-blocks filled in as N. Soft-masking has no
+    // place in a Helix, so it is dropped.
+    pub fn fetch_iupac(
+        &mut self,
+        name: &str,
+        start: usize,
+        end: usize,
+        cat: &IupacDNACat,
+    ) -> Result<Helix<IupacDNACell>, TwoBitError> {
+        let (bits, l) = self.bits(name, start, end)?;
+        let mut x: Vec<IupacDNACell> = bits
+            .into_iter()
+            .map(|b| cat.cell(&IupacDNA::from(DNA::from_bits(b))))
+            .collect();
+
+        for (s, n) in clip(&l.n, start, end) {
+            for y in x[s..s + n].iter_mut() {
+                *y = cat.n.clone();
+            }
+        }
+
+        Ok(x.into_iter().collect())
+    }
+
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+pub struct Entry<'a> {
+    pub name: String,
+    pub seq: &'a Helix<DNACell>,
+    // (start, size) blocks, as in Region.
+    pub n: Vec<(usize, usize)>,
+    pub mask: Vec<(usize, usize)>,
+}
+
+fn checked(x: usize, what: &str) -> io::Result<u32> {
+    u32::try_from(x).map_err(|_| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("{} {} does not fit in a .2bit file", what, x),
+        )
+    })
+}
+
+fn put_blocks<W: Write>(w: &mut W, blocks: &[(usize, usize)]) -> io::Result<()> {
+    w.write_all(&checked(blocks.len(), "block count")?.to_le_bytes())?;
+    for (s, _) in blocks.iter() {
+        w.write_all(&checked(*s, "block start")?.to_le_bytes())?;
+    }
+    for (_, n) in blocks.iter() {
+        w.write_all(&checked(*n, "block size")?.to_le_bytes())?;
+    }
+
+    Ok(())
+}
+
+// Where each entry's record starts, given 4 or 8 byte offsets in the index.
+fn offsets(names: &[&[u8]], entries: &[Entry], width: usize) -> Vec<u64> {
+    let mut offset = (16 + names.iter().map(|x| 1 + x.len() + width).sum::<usize>()) as u64;
+    let mut x = Vec::with_capacity(entries.len());
+    for e in entries.iter() {
+        x.push(offset);
+        offset +=
+            (4 + 4 + 8 * e.n.len() + 4 + 8 * e.mask.len() + 4 + e.seq.len().div_ceil(4)) as u64;
+    }
+
+    x
+}
+
+// NOTE: Writes a little endian file, version 0 while every record starts
+// under 4GB and version 1 (64 bit offsets) past that. Sequence lengths and
+// blocks are 32 bit in both, so larger ones are an InvalidInput error.
+// Names are cut to 255 bytes.
+pub fn write<W: Write>(w: &mut W, entries: &[Entry]) -> io::Result<()> {
+    let names: Vec<&[u8]> = entries
+        .iter()
+        .map(|e| &e.name.as_bytes()[..e.name.len().min(255)])
+        .collect();
+
+    let mut version = 0u32;
+    let mut starts = offsets(&names, entries, 4);
+    if starts.iter().any(|x| *x > u32::MAX as u64) {
+        version = 1;
+        starts = offsets(&names, entries, 8);
+    }
+
+    w.write_all(&SIGNATURE.to_le_bytes())?;
+    w.write_all(&version.to_le_bytes())?;
+    w.write_all(&checked(entries.len(), "sequence count")?.to_le_bytes())?;
+    w.write_all(&0u32.to_le_bytes())?;
+
+    for (name, offset) in names.iter().zip(starts.iter()) {
+        w.write_all(&[name.len() as u8])?;
+        w.write_all(name)?;
+        match version {
+            0 => w.write_all(&(*offset as u32).to_le_bytes())?,
+            _ => w.write_all(&offset.to_le_bytes())?,
+        }
+    }
+
+    for e in entries.iter() {
+        w.write_all(&checked(e.seq.len(), "sequence length")?.to_le_bytes())?;
+        put_blocks(w, &e.n)?;
+        put_blocks(w, &e.mask)?;
+        w.write_all(&0u32.to_le_bytes())?;
+        w.write_all(PackedStrand::from_helix(e.seq).as_bytes())?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use category::Cat;
+    use std::io::Cursor;
+
+    fn sample(c: &DNACat) -> (Helix<DNACell>, Helix<DNACell>, Vec<u8>) {
+        let a = c
+            .polymer_from_string(String::from("gattacaTTTTTcatgcatgca"))
+            .unwrap();
+        let b = c.polymer_from_string(String::from("acgtacg")).unwrap();
+
+        let mut out = Vec::new();
+        write(
+            &mut out,
+            &[
+                Entry {
+                    name: String::from("chr1"),
+                    seq: &a,
+                    n: vec![(7, 5)],
+                    mask: vec![(0, 3), (15, 7)],
+                },
+                Entry {
+                    name: String::from("chrM"),
+                    seq: &b,
+                    n: Vec::new(),
+                    mask: Vec::new(),
+                },
+            ],
+        )
+        .unwrap();
+
+        (a, b, out)
+    }
+
+    #[test]
+    fn write_and_fetch() {
+        let c = DNACat::new();
+        let (a, b, out) = sample(&c);
+        let mut r = Reader::new(Cursor::new(out)).unwrap();
+
+        assert_eq!(r.names(), &[String::from("chr1"), String::from("chrM")]);
+        assert_eq!(r.len("chrM").unwrap(), 7);
+        assert_eq!(r.fetch("chrM", 0, 7, &c).unwrap().seq, b);
+        assert_eq!(r.fetch("chr1", 0, 22, &c).unwrap().seq, a);
+
+        for (start, end) in [(1, 2), (3, 9), (5, 5), (13, 22)].iter() {
+            assert_eq!(
+                r.fetch("chr1", *start, *end, &c).unwrap().seq,
+                a.subsequence(*start..*end).unwrap()
+            );
+        }
+
+        let x = r.fetch("chr1", 2, 17, &c).unwrap();
+        assert_eq!(x.n, vec![(5, 5)]);
+        assert_eq!(x.mask, vec![(0, 1), (13, 2)]);
+    }
+
+    #[test]
+    fn fetch_iupac() {
+        let c = DNACat::new();
+        let ic = IupacDNACat::new();
+        let (_, _, out) = sample(&c);
+        let mut r = Reader::new(Cursor::new(out)).unwrap();
+
+        assert_eq!(
+            r.fetch_iupac("chr1", 5, 14, &ic).unwrap(),
+            ic.polymer_from_string(String::from("caNNNNNca")).unwrap()
+        );
+    }
+
+    #[test]
+    fn errors() {
+        let c = DNACat::new();
+        let (_, _, out) = sample(&c);
+        let mut r = Reader::new(Cursor::new(out)).unwrap();
+
+        match r.fetch("chr2", 0, 1, &c) {
+            Err(TwoBitError::Missing { name }) => assert_eq!(name, "chr2"),
+            y => panic!("expected a missing sequence, got {:?}", y),
+        }
+        match r.fetch("chrM", 3, 8, &c) {
+            Err(TwoBitError::Range {
+                start: 3,
+                end: 8,
+                len: 7,
+            }) => {}
+            y => panic!("expected a range error, got {:?}", y),
+        }
+        match Reader::new(Cursor::new(b">chr1\nACGT\n".to_vec())) {
+            Err(TwoBitError::Format { .. }) => {}
+            Err(e) => panic!("expected a format error, got {:?}", e),
+            Ok(_) => panic!("expected a format error"),
+        }
+
+        let mut w = Vec::new();
+        match put_blocks(&mut w, &[(0, u32::MAX as usize + 1)]) {
+            Err(e) => assert_eq!(e.kind(), io::ErrorKind::InvalidInput),
+            Ok(_) => panic!("expected an oversized block to be refused"),
+        }
+    }
+
+    #[test]
+    fn big_endian() {
+        let c = DNACat::new();
+        let mut f = Vec::new();
+        for x in [SIGNATURE, 0, 1, 0].iter() {
+            f.extend_from_slice(&x.to_be_bytes());
+        }
+        f.extend_from_slice(&[1, b'x']);
+        f.extend_from_slice(&22u32.to_be_bytes());
+        for x in [4u32, 0, 0, 0].iter() {
+            f.extend_from_slice(&x.to_be_bytes());
+        }
+        f.push(0b1001_1100);
+
+        let mut r = Reader::new(Cursor::new(f)).unwrap();
+        assert_eq!(
+            r.fetch("x", 0, 4, &c).unwrap().seq,
+            c.polymer_from_string(String::from("acgt")).unwrap()
+        );
+    }
+}
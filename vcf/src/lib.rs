@@ -0,0 +1,727 @@
+use category::Cat;
+use dna::{DNACat, DNACell};
+pub use format::FormatError;
+use format::Lines;
+use monomer::ParseError;
+use polymer::{Helix, Polymer};
+use std::error::Error;
+use std::fmt;
+use std::io::{self, BufRead};
+
+#[derive(Debug)]
+pub enum VcfError {
+    Read(FormatError),
+    // A REF or ALT column that is not DNA, the error's offset is into it.
+    Parse {
+        line: usize,
+        column: usize,
+        error: ParseError,
+    },
+}
+
+impl fmt::Display for VcfError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VcfError::Read(e) => write!(f, "{}", e),
+            VcfError::Parse {
+                line,
+                column,
+                error,
+            } => write!(f, "line {} column {}: {}", line, column, error),
+        }
+    }
+}
+
+impl Error for VcfError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            VcfError::Read(e) => Some(e),
+            VcfError::Parse { error, .. } => Some(error),
+        }
+    }
+}
+
+impl From<FormatError> for VcfError {
+    fn from(e: FormatError) -> Self {
+        VcfError::Read(e)
+    }
+}
+
+impl From<io::Error> for VcfError {
+    fn from(e: io::Error) -> Self {
+        VcfError::Read(e.into())
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Allele {
+    Seq(Helix<DNACell>),
+    // '*', the site falls in a deletion given by another record.
+    Overlapped,
+    // '.', no alternate allele.
+    Missing,
+    // Anything else, such as <DEL> or a breakend.
+    Symbolic(String),
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Kind {
+    SNV,
+    MNV,
+    Insertion,
+    Deletion,
+    // REF and ALT differ in length and in bases.
+    Complex,
+}
+
+// NOTE: An allele is None where the GT has '.', 0 is REF and n is the nth
+// ALT. A haploid call has one allele.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Genotype {
+    pub alleles: Vec<Option<usize>>,
+    pub phased: bool,
+}
+
+impl Genotype {
+    pub fn parse(s: &str) -> Option<Self> {
+        let phased = s.contains('|');
+        let alleles = s
+            .split(&['|', '/'][..])
+            .map(|x| match x {
+                "." => Some(None),
+                _ => x.parse().ok().map(Some),
+            })
+            .collect::<Option<Vec<_>>>()?;
+
+        Some(Genotype { alleles, phased })
+    }
+}
+
+// NOTE: pos is 1-based, as in the file. format and samples hold the raw
+// FORMAT keys and sample columns, genotypes the parsed GT of each sample
+// (empty if the record has no GT).
+#[derive(Debug, PartialEq)]
+pub struct Record {
+    pub chrom: String,
+    pub pos: usize,
+    pub ids: Vec<String>,
+    pub reference: Helix<DNACell>,
+    pub alts: Vec<Allele>,
+    pub qual: Option<f64>,
+    pub filters: Vec<String>,
+    pub info: Vec<(String, Option<String>)>,
+    pub format: Vec<String>,
+    pub samples: Vec<Vec<String>>,
+    pub genotypes: Vec<Genotype>,
+}
+
+// Lengths of the prefix, then the suffix of what is left, that a and b share.
+fn shared(a: &Helix<DNACell>, b: &Helix<DNACell>) -> (usize, usize) {
+    let n = a.len().min(b.len());
+    let p = (0..n).take_while(|i| a.get(*i) == b.get(*i)).count();
+    let s = (0..n - p)
+        .take_while(|i| a.get(a.len() - 1 - i) == b.get(b.len() - 1 - i))
+        .count();
+
+    (p, s)
+}
+
+impl Record {
+    pub fn info(&self, key: &str) -> Option<&Option<String>> {
+        self.info.iter().find(|(k, _)| k == key).map(|(_, v)| v)
+    }
+
+    // The 0-based [start, end) of the reference this record replaces.
+    pub fn span(&self) -> (usize, usize) {
+        (self.pos - 1, self.pos - 1 + self.reference.len())
+    }
+
+    // NOTE: alt is 1-based, as in GT. None for REF and non-sequence alleles.
+    pub fn kind(&self, alt: usize) -> Option<Kind> {
+        let a = match self.alts.get(alt.checked_sub(1)?)? {
+            Allele::Seq(a) => a,
+            _ => return None,
+        };
+
+        let r = &self.reference;
+        if r.len() == a.len() {
+            return match r.len() {
+                1 => Some(Kind::SNV),
+                _ => Some(Kind::MNV),
+            };
+        }
+
+        let (p, s) = shared(r, a);
+        match (r.len() - p - s, a.len() - p - s) {
+            (0, _) => Some(Kind::Insertion),
+            (_, 0) => Some(Kind::Deletion),
+            _ => Some(Kind::Complex),
+        }
+    }
+}
+
+fn record(l: &str, line: usize, cat: &DNACat, samples: usize) -> Result<Record, VcfError> {
+    let columns: Vec<&str> = l.split('\t').collect();
+    let err = |column: usize, expected: &'static str| {
+        VcfError::from(FormatError::Format {
+            line,
+            column,
+            expected,
+        })
+    };
+
+    if columns.len() < 8 {
+        return Err(err(
+            columns.len() + 1,
+            "at least eight tab separated columns",
+        ));
+    }
+    if columns.len() != 8 && columns.len() != 9 + samples {
+        return Err(err(columns.len().min(9 + samples), "a column per sample"));
+    }
+
+    let pos: usize = match columns[1].parse() {
+        Ok(0) | Err(_) => return Err(err(2, "a position from 1")),
+        Ok(x) => x,
+    };
+
+    let list = |s: &str, sep: char| match s {
+        "." => Vec::new(),
+        _ => s.split(sep).map(|x| x.to_string()).collect(),
+    };
+
+    let reference = cat
+        .polymer_from_string(columns[3].to_string())
+        .map_err(|error| VcfError::Parse {
+            line,
+            column: 4,
+            error,
+        })?;
+    if reference.is_empty() {
+        return Err(err(4, "a reference allele"));
+    }
+
+    let mut alts = Vec::new();
+    let mut offset = 0;
+    for x in columns[4].split(',') {
+        alts.push(match x {
+            "*" => Allele::Overlapped,
+            "." => Allele::Missing,
+            _ if x.starts_with('<') || x.contains(&['[', ']'][..]) => {
+                Allele::Symbolic(x.to_string())
+            }
+            _ => Allele::Seq(cat.polymer_from_string(x.to_string()).map_err(|e| {
+                VcfError::Parse {
+                    line,
+                    column: 5,
+                    error: e.offset_by(offset),
+                }
+            })?),
+        });
+        offset += x.len() + 1;
+    }
+
+    let qual = match columns[5] {
+        "." => None,
+        x => Some(x.parse().map_err(|_| err(6, "a quality or '.'"))?),
+    };
+
+    let info = match columns[7] {
+        "." => Vec::new(),
+        x => x
+            .split(';')
+            .map(|y| match y.find('=') {
+                Some(i) => (y[..i].to_string(), Some(y[i + 1..].to_string())),
+                None => (y.to_string(), None),
+            })
+            .collect(),
+    };
+
+    let format = columns.get(8).map(|x| list(x, ':')).unwrap_or_default();
+    let samples: Vec<Vec<String>> = columns
+        .iter()
+        .skip(9)
+        .map(|x| x.split(':').map(|y| y.to_string()).collect())
+        .collect();
+
+    let mut genotypes = Vec::new();
+    if format.first().map(|x| x.as_str()) == Some("GT") {
+        for (i, s) in samples.iter().enumerate() {
+            let g = s
+                .first()
+                .and_then(|x| Genotype::parse(x))
+                .ok_or_else(|| err(10 + i, "a genotype"))?;
+            if g.alleles.iter().flatten().any(|a| *a > alts.len()) {
+                return Err(err(10 + i, "a genotype of known alleles"));
+            }
+            genotypes.push(g);
+        }
+    }
+
+    Ok(Record {
+        chrom: columns[0].to_string(),
+        pos,
+        ids: list(columns[2], ';'),
+        reference,
+        alts,
+        qual,
+        filters: list(columns[6], ';'),
+        info,
+        format,
+        samples,
+        genotypes,
+    })
+}
+
+// NOTE: The header is read by new, up to and including the #CHROM line.
+// Records then stream. Each record is a line of its own, so a bad one is an
+// error and reading goes on with the next, only an io error ends the stream.
+// REF and ALT must be plain DNA, so a record with N in them is a Parse error
+// and is passed over like any other bad record.
+pub struct Reader<'a, R> {
+    lines: Lines<R>,
+    cat: &'a DNACat,
+    meta: Vec<String>,
+    samples: Vec<String>,
+}
+
+impl<'a, R: BufRead> Reader<'a, R> {
+    pub fn new(inner: R, cat: &'a DNACat) -> Result<Self, VcfError> {
+        let mut lines = Lines::new(inner);
+        let mut meta = Vec::new();
+        let err = |line: usize| {
+            VcfError::from(FormatError::Format {
+                line,
+                column: 1,
+                expected: "a #CHROM header",
+            })
+        };
+
+        let samples = loop {
+            let (line, l) = match lines.next() {
+                Some(x) => x?,
+                None => return Err(err(lines.line() + 1)),
+            };
+
+            if let Some(x) = l.strip_prefix("##") {
+                meta.push(x.to_string());
+            } else if l.starts_with("#CHROM") {
+                break l.split('\t').skip(9).map(|x| x.to_string()).collect();
+            } else {
+                return Err(err(line));
+            }
+        };
+
+        Ok(Reader {
+            lines,
+            cat,
+            meta,
+            samples,
+        })
+    }
+
+    // The ## lines, without the ##.
+    pub fn meta(&self) -> &[String] {
+        &self.meta
+    }
+
+    pub fn samples(&self) -> &[String] {
+        &self.samples
+    }
+}
+
+impl<'a, R: BufRead> Iterator for Reader<'a, R> {
+    type Item = Result<Record, VcfError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for x in self.lines.by_ref() {
+            let (n, l) = match x {
+                Ok(y) => y,
+                Err(e) => return Some(Err(e.into())),
+            };
+
+            if l.trim().is_empty() {
+                continue;
+            }
+
+            return Some(record(&l, n, self.cat, self.samples.len()));
+        }
+
+        None
+    }
+}
+
+// NOTE: Sorted, non-overlapping (reference start, haplotype start, length)
+// blocks of bases that line up one to one. Bases outside every block were
+// deleted from, or inserted into, the haplotype.
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct CoordinateMap {
+    blocks: Vec<(usize, usize, usize)>,
+}
+
+impl CoordinateMap {
+    fn push(&mut self, r: usize, h: usize, len: usize) {
+        if len == 0 {
+            return;
+        }
+        if let Some(x) = self.blocks.last_mut() {
+            if x.0 + x.2 == r && x.1 + x.2 == h {
+                x.2 += len;
+                return;
+            }
+        }
+        self.blocks.push((r, h, len));
+    }
+
+    pub fn blocks(&self) -> &[(usize, usize, usize)] {
+        &self.blocks
+    }
+
+    pub fn to_haplotype(&self, r: usize) -> Option<usize> {
+        let i = self.blocks.partition_point(|x| x.0 + x.2 <= r);
+        let (rs, hs, _) = self.blocks.get(i).filter(|x| x.0 <= r)?;
+        Some(hs + r - rs)
+    }
+
+    pub fn to_reference(&self, h: usize) -> Option<usize> {
+        let i = self.blocks.partition_point(|x| x.1 + x.2 <= h);
+        let (rs, hs, _) = self.blocks.get(i).filter(|x| x.1 <= h)?;
+        Some(rs + h - hs)
+    }
+}
+
+// NOTE: record is an index into the records given to apply.
+#[derive(Debug, PartialEq, Clone)]
+pub enum Conflict {
+    // REF is not what the reference has at pos.
+    RefMismatch { record: usize },
+    // REF runs past the end of the reference.
+    OutOfRange { record: usize },
+    // The record overlaps one already applied to this haplotype.
+    Overlap { record: usize, with: usize },
+    // The allele called is symbolic, or '*' or '.'.
+    Unsupported { record: usize },
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Haplotype {
+    pub seq: Helix<DNACell>,
+    pub map: CoordinateMap,
+    // Indices of the records applied, in reference order.
+    pub applied: Vec<usize>,
+    pub conflicts: Vec<Conflict>,
+}
+
+// NOTE: Builds one haplotype per GT allele of the sample, as many as the
+// highest ploidy among the records. reference is the sequence of chrom, and
+// records on other chroms are skipped, so a whole file can be given once per
+// chrom; record indices count every record given. Records are taken in order
+// of position, and only where the haplotype carries an ALT; a record that
+// conflicts is reported and left out, so the rest still apply.
+pub fn apply<'r, I>(
+    reference: &Helix<DNACell>,
+    chrom: &str,
+    records: I,
+    sample: usize,
+) -> Vec<Haplotype>
+where
+    I: IntoIterator<Item = &'r Record>,
+{
+    let mut records: Vec<(usize, &Record)> = records
+        .into_iter()
+        .enumerate()
+        .filter(|(_, x)| x.chrom == chrom)
+        .collect();
+    records.sort_by_key(|(_, x)| x.pos);
+
+    let ploidy = records
+        .iter()
+        .filter_map(|(_, x)| x.genotypes.get(sample))
+        .map(|g| g.alleles.len())
+        .max()
+        .unwrap_or(0);
+
+    (0..ploidy)
+        .map(|h| haplotype(reference, &records, sample, h))
+        .collect()
+}
+
+fn haplotype(
+    reference: &Helix<DNACell>,
+    records: &[(usize, &Record)],
+    sample: usize,
+    h: usize,
+) -> Haplotype {
+    let mut x = Haplotype {
+        seq: Helix::new(),
+        map: CoordinateMap::default(),
+        applied: Vec::new(),
+        conflicts: Vec::new(),
+    };
+
+    // How far along the reference has been copied, and by which record.
+    let mut done = 0;
+    let mut last = None;
+
+    for (i, r) in records.iter() {
+        let allele = match r
+            .genotypes
+            .get(sample)
+            .and_then(|g| g.alleles.get(h))
+            .copied()
+            .flatten()
+        {
+            None | Some(0) => continue,
+            Some(a) => a,
+        };
+
+        let alt = match &r.alts[allele - 1] {
+            Allele::Seq(a) => a,
+            _ => {
+                x.conflicts.push(Conflict::Unsupported { record: *i });
+                continue;
+            }
+        };
+
+        let (start, end) = r.span();
+        if end > reference.len() {
+            x.conflicts.push(Conflict::OutOfRange { record: *i });
+            continue;
+        }
+        if start < done {
+            x.conflicts.push(Conflict::Overlap {
+                record: *i,
+                with: last.unwrap(),
+            });
+            continue;
+        }
+        if reference.subsequence(start..end).as_ref() != Some(&r.reference) {
+            x.conflicts.push(Conflict::RefMismatch { record: *i });
+            continue;
+        }
+
+        x.map.push(done, x.seq.len(), start - done);
+        for b in &reference.subsequence(done..start).unwrap() {
+            x.seq.push(b.clone());
+        }
+
+        // Shared ends line up. What is left lines up too if it is a
+        // substitution, and is otherwise a deletion and insertion.
+        let hs = x.seq.len();
+        let (p, s) = shared(&r.reference, alt);
+        match r.reference.len() == alt.len() {
+            true => x.map.push(start, hs, alt.len()),
+            _ => {
+                x.map.push(start, hs, p);
+                x.map.push(end - s, hs + alt.len() - s, s);
+            }
+        }
+        for b in alt {
+            x.seq.push(b.clone());
+        }
+
+        x.applied.push(*i);
+        done = end;
+        last = Some(*i);
+    }
+
+    x.map.push(done, x.seq.len(), reference.len() - done);
+    for b in &reference.subsequence(done..).unwrap() {
+        x.seq.push(b.clone());
+    }
+
+    x
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    //                    0         1         2
+    //                    012345678901234567890123
+    const GENOME: &str = "ACGTACGTACGTACGTACGTACGT";
+
+    const INPUT: &str = "\
+##fileformat=VCFv4.2
+##contig=<ID=chr1,length=24>
+#CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO\tFORMAT\tNA1\tNA2
+chr1\t2\trs1\tC\tT\t50\tPASS\tDP=10;DB\tGT:DP\t0|1:5\t1/1:3
+chr1\t4\t.\tTA\tGC\t.\t.\t.\tGT\t1|0\t0/0
+chr1\t7\t.\tG\tGAAA,T\t.\t.\t.\tGT\t2|1\t./.
+chr1\t7\t.\tG\tC\t.\t.\t.\tGT\t1|0\t0/0
+chr1\t11\t.\tGTAC\tG\t.\t.\t.\tGT\t1|0\t0/1
+chr1\t18\t.\tCG\tAG\t.\t.\t.\tGT\t0|1\t0/0
+";
+
+    fn read(input: &str) -> Vec<Result<Record, VcfError>> {
+        let c = DNACat::new();
+        Reader::new(input.as_bytes(), &c).unwrap().collect()
+    }
+
+    fn seq(s: &str) -> Helix<DNACell> {
+        DNACat::new().polymer_from_string(String::from(s)).unwrap()
+    }
+
+    #[test]
+    fn read_records() {
+        let c = DNACat::new();
+        let r = Reader::new(INPUT.as_bytes(), &c).unwrap();
+        assert_eq!(r.meta().len(), 2);
+        assert_eq!(r.samples(), &[String::from("NA1"), String::from("NA2")]);
+
+        let x: Vec<Record> = r.collect::<Result<_, _>>().unwrap();
+        assert_eq!(x.len(), 6);
+        assert_eq!(x[0].ids, vec![String::from("rs1")]);
+        assert_eq!(x[0].qual, Some(50.0));
+        assert_eq!(x[0].info("DP"), Some(&Some(String::from("10"))));
+        assert_eq!(x[0].info("DB"), Some(&None));
+        assert_eq!(
+            x[0].samples[1],
+            vec![String::from("1/1"), String::from("3")]
+        );
+        assert_eq!(
+            x[2].genotypes[0],
+            Genotype {
+                alleles: vec![Some(2), Some(1)],
+                phased: true
+            }
+        );
+        assert_eq!(x[2].genotypes[1].alleles, vec![None, None]);
+
+        assert_eq!(x[0].kind(1), Some(Kind::SNV));
+        assert_eq!(x[1].kind(1), Some(Kind::MNV));
+        assert_eq!(x[2].kind(1), Some(Kind::Insertion));
+        assert_eq!(x[2].kind(2), Some(Kind::SNV));
+        assert_eq!(x[4].kind(1), Some(Kind::Deletion));
+        assert_eq!(x[4].kind(0), None);
+    }
+
+    #[test]
+    fn read_errors() {
+        let header = "#CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO\tFORMAT\tNA1\n";
+
+        match &read(&format!("{}chr1\t0\t.\tA\tC\t.\t.\t.\tGT\t0\n", header))[..] {
+            [Err(VcfError::Read(FormatError::Format {
+                line: 2, column: 2, ..
+            }))] => {}
+            y => panic!("expected a format error, got {:?}", y),
+        }
+        match &read(&format!("{}chr1\t1\t.\tA\tC,GX\t.\t.\t.\tGT\t0\n", header))[..] {
+            [Err(VcfError::Parse {
+                line: 2,
+                column: 5,
+                error: ParseError::Monomer { offset: 3, .. },
+            })] => {}
+            y => panic!("expected a parse error, got {:?}", y),
+        }
+        match &read(&format!("{}chr1\t1\t.\tA\tC\t.\t.\t.\tGT\t0|2\n", header))[..] {
+            [Err(VcfError::Read(FormatError::Format {
+                line: 2,
+                column: 10,
+                ..
+            }))] => {}
+            y => panic!("expected a format error, got {:?}", y),
+        }
+        match &read(&format!("{}chr1\t1\t.\tA\tC\t.\t.\t.\tGT\n", header))[..] {
+            [Err(VcfError::Read(FormatError::Format { line: 2, .. }))] => {}
+            y => panic!("expected a format error, got {:?}", y),
+        }
+
+        let c = DNACat::new();
+        assert!(Reader::new("chr1\t1\t.\tA\tC\n".as_bytes(), &c).is_err());
+    }
+
+    #[test]
+    fn read_past_bad_records() {
+        let x = read(concat!(
+            "#CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO\tFORMAT\tNA1\n",
+            "chr1\t1\t.\tN\tC\t.\t.\t.\tGT\t0|1\n",
+            "chr1\t2\t.\tC\tT\t.\t.\t.\tGT\t0|3\n",
+            "chr1\t3\t.\tG\tA\t.\t.\t.\tGT\t1|1\n",
+        ));
+        assert_eq!(x.len(), 3);
+        match &x[0] {
+            Err(VcfError::Parse {
+                line: 2, column: 4, ..
+            }) => {}
+            y => panic!("expected a parse error, got {:?}", y),
+        }
+        match &x[1] {
+            Err(VcfError::Read(FormatError::Format {
+                line: 3,
+                column: 10,
+                ..
+            })) => {}
+            y => panic!("expected a format error, got {:?}", y),
+        }
+        assert_eq!(x[2].as_ref().unwrap().pos, 3);
+    }
+
+    #[test]
+    fn apply_haplotypes() {
+        let genome = seq(GENOME);
+        let x: Vec<Record> = read(INPUT).into_iter().collect::<Result<_, _>>().unwrap();
+        let h = apply(&genome, "chr1", &x, 0);
+
+        assert_eq!(h.len(), 2);
+
+        // 1|0 at 4, 2|1 at 7 (and 1|0 at 7 overlapping), 1|0 at 11.
+        assert_eq!(h[0].seq, seq("ACGGCCTTACGGTACGTACGT"));
+        assert_eq!(h[0].applied, vec![1, 2, 4]);
+        assert_eq!(
+            h[0].conflicts,
+            vec![Conflict::Overlap { record: 3, with: 2 }]
+        );
+
+        // 0|1 at 2, 2|1 at 7, 0|1 at 18.
+        assert_eq!(h[1].seq, seq("ATGTACGAAATACGTACGTAAGTACGT"));
+        assert_eq!(h[1].applied, vec![0, 2, 5]);
+        assert!(h[1].conflicts.is_empty());
+
+        // The deletion of TAC at 11..14 on the first haplotype.
+        let m = &h[0].map;
+        assert_eq!(m.to_haplotype(10), Some(10));
+        assert_eq!(m.to_haplotype(11), None);
+        assert_eq!(m.to_haplotype(13), None);
+        assert_eq!(m.to_haplotype(14), Some(11));
+        assert_eq!(m.to_reference(11), Some(14));
+        assert_eq!(m.to_haplotype(23), Some(20));
+
+        // The insertion of AAA after 6 on the second haplotype.
+        let m = &h[1].map;
+        assert_eq!(m.to_haplotype(6), Some(6));
+        assert_eq!(m.to_reference(7), None);
+        assert_eq!(m.to_reference(9), None);
+        assert_eq!(m.to_haplotype(7), Some(10));
+        assert_eq!(m.to_haplotype(1), Some(1));
+        assert_eq!(m.blocks()[0], (0, 0, 7));
+    }
+
+    #[test]
+    fn apply_conflicts() {
+        let genome = seq(GENOME);
+        let input = "\
+#CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO\tFORMAT\tNA1
+chr1\t1\t.\tG\tT\t.\t.\t.\tGT\t1
+chr1\t24\t.\tTA\tT\t.\t.\t.\tGT\t1
+chr1\t3\t.\tG\t<DEL>\t.\t.\t.\tGT\t1
+chr1\t5\t.\tA\tT\t.\t.\t.\tGT\t1
+chr2\t7\t.\tG\tC\t.\t.\t.\tGT\t1/1
+";
+        let x: Vec<Record> = read(input).into_iter().collect::<Result<_, _>>().unwrap();
+        assert!(apply(&genome, "chr3", &x, 0).is_empty());
+        let h = apply(&genome, "chr1", &x, 0);
+
+        assert_eq!(h.len(), 1);
+        assert_eq!(h[0].seq, seq("ACGTTCGTACGTACGTACGTACGT"));
+        assert_eq!(
+            h[0].conflicts,
+            vec![
+                Conflict::RefMismatch { record: 0 },
+                Conflict::Unsupported { record: 2 },
+                Conflict::OutOfRange { record: 1 },
+            ]
+        );
+        assert_eq!(h[0].map.blocks(), &[(0, 0, 24)]);
+    }
+}